@@ -4,7 +4,7 @@ use {
     alloy_chains::NamedChain,
     alloy_primitives::U256,
     alloy_provider::WalletProvider,
-    cctp_client::{Cctp, SolanaWrapper},
+    cctp_client::{Cctp, SolanaRecipientAccount, SolanaWrapper, TransferMode},
     solana_signer::Signer,
     tracing::info,
 };
@@ -31,8 +31,15 @@ async fn main() -> anyhow::Result<()> {
         cctp_client::SOLANA_DEVNET,
     );
     let result = bridge
-        .bridge_evm_sol(&owner, U256::from(10), None, None, None)
-        .await?;
+        .bridge_evm_sol(
+            &owner,
+            SolanaRecipientAccount::CreateAssociated { payer: None },
+            U256::from(10),
+            None,
+            TransferMode::Standard,
+        )
+        .await?
+        .broadcast()?;
     println!("success {result}");
     Ok(())
 }
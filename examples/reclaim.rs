@@ -14,8 +14,11 @@ async fn main() -> anyhow::Result<()> {
     info!("solana address {}", owner.pubkey(),);
     let rpc: SolanaWrapper = rpc.into();
 
-    let bridge = Cctp::new_reclaim(rpc.clone(), rpc, cctp_client::SOLANA_DEVNET);
-    let result = bridge.reclaim(&owner).await?;
+    let bridge = Cctp::builder()
+        .with_source_provider(rpc)
+        .with_source_chain(cctp_client::SOLANA_DEVNET)
+        .build()?;
+    let result = bridge.reclaim(&owner).await?.broadcast()?;
     println!("reclaimed {} accounts", result.len());
     for (sig, addr) in result {
         println!("reclaimed account {} with signature {}", addr, sig);
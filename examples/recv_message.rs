@@ -11,18 +11,18 @@ async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
     let (owner, rpc) = common::solana_setup()?;
     let rpc: SolanaWrapper = rpc.into();
-    let bridge = Cctp::new_recv(
-        rpc.clone(),
-        rpc,
-        NamedChain::BaseSepolia,
-        cctp_client::SOLANA_DEVNET,
-    );
+    let bridge = Cctp::builder()
+        .with_destination_provider(rpc)
+        .with_source_chain(NamedChain::BaseSepolia)
+        .with_destination_chain(cctp_client::SOLANA_DEVNET)
+        .build()?;
     let result = bridge
         .recv_message_sol(
             &owner,
             "0x1de765f7d19b45913190863d8cd60c1e58e48a85b60b0ff7bf39329076aabd7b",
         )
-        .await?;
-    println!("{result}");
+        .await?
+        .broadcast()?;
+    println!("{}", result.tx);
     Ok(())
 }
@@ -3,7 +3,7 @@ mod common;
 use {
     alloy_chains::NamedChain,
     alloy_provider::WalletProvider,
-    cctp_client::{Cctp, SolanSigners},
+    cctp_client::{Cctp, SolanSigners, TransferMode},
     common::*,
     solana_signer::Signer,
     tracing::info,
@@ -34,7 +34,10 @@ async fn main() -> anyhow::Result<()> {
         NamedChain::BaseSepolia,
     );
     // 0.000010 USDC to base sepolia
-    let result = bridge.bridge_sol_evm(10, signers, None, None, None).await?;
+    let result = bridge
+        .bridge_sol_evm(10, signers, None, TransferMode::Standard)
+        .await?
+        .broadcast()?;
     println!("Solana burn txHash {}", result.burn);
     println!(
         "Base Receive txHash {}",
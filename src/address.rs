@@ -1,23 +1,156 @@
-use {crate::error::Error, alloy_primitives::FixedBytes, std::fmt::Display};
+use {
+    crate::error::Error,
+    alloy_primitives::{FixedBytes, hex},
+    bech32::{Bech32, Hrp},
+    serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _},
+    std::{fmt::Display, str::FromStr},
+};
+
+/// Most bytes an [`Address`] holds
+pub const MAX_ADDRESS_BYTES: usize = 64;
+
+/// Bech32 prefix of Noble accounts
+pub const NOBLE_HRP: Hrp = Hrp::parse_unchecked("noble");
+
+/// The chain family of an [`Address`], which decides its length and format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AddressKind {
+    /// 20 byte EVM address, formatted as `0x` hex
+    Evm,
+    /// 32 byte Solana public key, formatted as base58
+    Solana,
+    /// 20 byte Noble account, formatted as bech32 with the [`NOBLE_HRP`]
+    /// prefix
+    Noble,
+    /// 32 byte Sui or Aptos account, formatted as `0x` hex
+    Move,
+    /// Raw bytes of the given length, formatted as `0x` hex
+    Other(usize),
+}
+
+impl AddressKind {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Evm | Self::Noble => 20,
+            Self::Solana => solana_pubkey::PUBKEY_BYTES,
+            Self::Move => 32,
+            Self::Other(len) => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for AddressKind {
+    fn default() -> Self {
+        Self::Other(0)
+    }
+}
+
+/// Error parsing an [`Address`] from a string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseAddressError {
+    #[error("empty address")]
+    Empty,
+    #[error("invalid hex address {input}: {source}")]
+    Hex {
+        input: String,
+        #[source]
+        source: hex::FromHexError,
+    },
+    #[error("address {input} has {len} bytes, at most {MAX_ADDRESS_BYTES} are supported")]
+    Length { input: String, len: usize },
+    #[error("invalid bech32 address {input}: {source}")]
+    Bech32 {
+        input: String,
+        #[source]
+        source: bech32::DecodeError,
+    },
+    #[error("bech32 address {0} is not a Noble account")]
+    Bech32Account(String),
+    #[error("unrecognized address format {0}")]
+    Format(String),
+}
 
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
-pub struct Address(pub FixedBytes<64>, pub usize);
+pub struct Address(FixedBytes<MAX_ADDRESS_BYTES>, AddressKind);
 
 impl Address {
+    /// Parses `value`, see [`Address::from_str`]
+    #[deprecated(note = "panics on invalid input, use `str::parse` instead")]
     pub fn new(value: impl Into<String>) -> Self {
-        // For backward compatibility with string-based construction
         let s = value.into();
-        if let Ok(addr) = s.parse::<alloy_primitives::Address>() {
-            Self::from(addr)
-        } else if let Ok(pubkey) = s.parse::<solana_pubkey::Pubkey>() {
-            Self::from(pubkey)
-        } else {
-            panic!("Invalid address format: {}", s)
+        s.parse()
+            .unwrap_or_else(|e| panic!("Invalid address format: {e}"))
+    }
+
+    /// Wraps raw bytes, fails when there are more than [`MAX_ADDRESS_BYTES`]
+    pub fn from_slice(bytes: &[u8], kind: AddressKind) -> Result<Self, Error> {
+        if bytes.len() != kind.len() || bytes.len() > MAX_ADDRESS_BYTES {
+            return Err(Error::AddrError(format!(
+                "{} bytes do not fit a {kind:?} address",
+                bytes.len()
+            )));
+        }
+        let mut buf = FixedBytes::<MAX_ADDRESS_BYTES>::ZERO;
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self(buf, kind))
+    }
+
+    pub fn kind(&self) -> AddressKind {
+        self.1
+    }
+
+    /// The address as `kind`, which must be its kind or, for raw
+    /// [`AddressKind::Other`] bytes such as a parsed 32 byte hex Sui or
+    /// Aptos account, have the length of `kind`
+    pub fn with_kind(self, kind: AddressKind) -> Result<Self, Error> {
+        match self.1 {
+            current if current == kind => Ok(self),
+            AddressKind::Other(len) if len == kind.len() => Ok(Self(self.0, kind)),
+            current => Err(Error::AddrError(format!(
+                "{self} is a {current:?} address, not {kind:?}"
+            ))),
         }
     }
 
     pub fn as_bytes(&self) -> &[u8] {
-        &self.0[..self.1]
+        &self.0[..self.1.len()]
+    }
+
+    /// The left zero padded bytes32 form used by CCTP for `mintRecipient`,
+    /// `destinationCaller` and message senders
+    pub fn to_bytes32(&self) -> Result<FixedBytes<32>, Error> {
+        let bytes = self.as_bytes();
+        if bytes.len() > 32 {
+            return Err(Error::AddrError(format!(
+                "{self} has {} bytes, does not fit bytes32",
+                bytes.len()
+            )));
+        }
+        let mut word = FixedBytes::<32>::ZERO;
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        Ok(word)
+    }
+
+    /// Reads an address of `kind` from its bytes32 form, the padding must be
+    /// zero
+    pub fn from_bytes32(word: FixedBytes<32>, kind: AddressKind) -> Result<Self, Error> {
+        let len = kind.len();
+        if len > 32 {
+            return Err(Error::AddrError(format!(
+                "{kind:?} address does not fit bytes32"
+            )));
+        }
+        let (padding, bytes) = word.split_at(32 - len);
+        if padding.iter().any(|b| *b != 0) {
+            return Err(Error::AddrError(format!(
+                "{word} is not a left padded {kind:?} address"
+            )));
+        }
+        Self::from_slice(bytes, kind)
     }
 }
 
@@ -30,38 +163,127 @@ impl AsRef<Address> for Address {
 impl Display for Address {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.1 {
-            20 => {
-                // EVM address - format as hex with 0x prefix
-                let addr = alloy_primitives::Address::from_slice(&self.0[..20]);
+            AddressKind::Evm => {
+                let addr = alloy_primitives::Address::from_slice(self.as_bytes());
                 write!(f, "{:#x}", addr)
             }
-            solana_pubkey::PUBKEY_BYTES => {
-                // Solana address - format as base58
+            AddressKind::Solana => {
                 let mut bytes = [0u8; 32];
-                bytes.copy_from_slice(&self.0[..32]);
+                bytes.copy_from_slice(self.as_bytes());
                 let pubkey = solana_pubkey::Pubkey::new_from_array(bytes);
                 write!(f, "{}", pubkey)
             }
-            _ => {
-                // Generic hex format for other lengths
-                write!(f, "0x{}", alloy_primitives::hex::encode(self.as_bytes()))
+            AddressKind::Noble => {
+                let encoded = bech32::encode::<Bech32>(NOBLE_HRP, self.as_bytes())
+                    .map_err(|_| std::fmt::Error)?;
+                f.write_str(&encoded)
+            }
+            AddressKind::Move | AddressKind::Other(_) => {
+                write!(f, "0x{}", hex::encode(self.as_bytes()))
+            }
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = ParseAddressError;
+
+    /// Parses a `0x` hex EVM address, a base58 Solana public key, a bech32
+    /// Noble account, or other `0x` hex bytes. 32 byte hex is ambiguous and
+    /// parses as [`AddressKind::Other`], see [`Address::with_kind`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseAddressError::Empty);
+        }
+        if let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let bytes = hex::decode(digits).map_err(|source| ParseAddressError::Hex {
+                input: s.to_string(),
+                source,
+            })?;
+            let kind = match bytes.len() {
+                20 => AddressKind::Evm,
+                len if len > MAX_ADDRESS_BYTES => {
+                    return Err(ParseAddressError::Length {
+                        input: s.to_string(),
+                        len,
+                    });
+                }
+                len => AddressKind::Other(len),
+            };
+            let mut buf = FixedBytes::<MAX_ADDRESS_BYTES>::ZERO;
+            buf[..bytes.len()].copy_from_slice(&bytes);
+            return Ok(Self(buf, kind));
+        }
+        if s.to_ascii_lowercase().starts_with("noble1") {
+            let (hrp, bytes) = bech32::decode(s).map_err(|source| ParseAddressError::Bech32 {
+                input: s.to_string(),
+                source,
+            })?;
+            if hrp != NOBLE_HRP || bytes.len() != AddressKind::Noble.len() {
+                return Err(ParseAddressError::Bech32Account(s.to_string()));
             }
+            let mut buf = FixedBytes::<MAX_ADDRESS_BYTES>::ZERO;
+            buf[..bytes.len()].copy_from_slice(&bytes);
+            return Ok(Self(buf, AddressKind::Noble));
+        }
+        s.parse::<solana_pubkey::Pubkey>()
+            .map(Self::from)
+            .map_err(|_| ParseAddressError::Format(s.to_string()))
+    }
+}
+
+impl Serialize for Address {
+    /// Human readable formats get the [`Display`] string, binary formats the
+    /// kind and raw bytes
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.1, self.as_bytes()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(D::Error::custom)
+        } else {
+            let (kind, bytes) = <(AddressKind, Vec<u8>)>::deserialize(deserializer)?;
+            Self::from_slice(&bytes, kind).map_err(D::Error::custom)
         }
     }
 }
 
+/// Serde with `0x` hex strings of the raw bytes in every format, e.g. for
+/// Solana keys in EVM tooling. `#[serde(with = "cctp_client::address_hex")]`
+pub mod address_hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(address: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(address.as_bytes())))
+    }
+
+    /// 20 bytes are read as an EVM address, 32 bytes as a Solana key
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&s).map_err(D::Error::custom)?;
+        let kind = match bytes.len() {
+            20 => AddressKind::Evm,
+            32 => AddressKind::Solana,
+            len => AddressKind::Other(len),
+        };
+        Address::from_slice(&bytes, kind).map_err(D::Error::custom)
+    }
+}
+
 impl TryFrom<Address> for alloy_primitives::Address {
     type Error = Error;
 
     fn try_from(addr: Address) -> Result<Self, Self::Error> {
-        if addr.1 == 20 {
-            Ok(alloy_primitives::Address::from_slice(&addr.0[..20]))
-        } else {
-            Err(Error::AddrError(format!(
-                "Invalid length for EVM address: expected 20, got {}",
-                addr.1
-            )))
-        }
+        Self::try_from(&addr)
     }
 }
 
@@ -69,11 +291,11 @@ impl TryFrom<&Address> for alloy_primitives::Address {
     type Error = Error;
 
     fn try_from(addr: &Address) -> Result<Self, Self::Error> {
-        if addr.1 == 20 {
-            Ok(alloy_primitives::Address::from_slice(&addr.0[..20]))
+        if addr.1 == AddressKind::Evm {
+            Ok(alloy_primitives::Address::from_slice(addr.as_bytes()))
         } else {
             Err(Error::AddrError(format!(
-                "Invalid length for EVM address: expected 20, got {}",
+                "Invalid EVM address: expected Evm, got {:?} ({addr})",
                 addr.1
             )))
         }
@@ -85,17 +307,6 @@ impl TryFrom<Address> for solana_pubkey::Pubkey {
 
     fn try_from(addr: Address) -> Result<Self, Self::Error> {
         Self::try_from(&addr)
-        // if addr.1 == solana_pubkey::PUBKEY_BYTES {
-        // let mut bytes = [0u8; 32];
-        // bytes.copy_from_slice(&addr.0[..32]);
-        // Ok(solana_pubkey::Pubkey::new_from_array(bytes))
-        // } else {
-        // Err(CctpError::AddrError(format!(
-        // "Invalid length for Solana address: expected {}, got {} ({addr})",
-        // solana_pubkey::PUBKEY_BYTES,
-        // addr.1
-        // )))
-        // }
     }
 }
 
@@ -103,14 +314,13 @@ impl TryFrom<&Address> for solana_pubkey::Pubkey {
     type Error = Error;
 
     fn try_from(addr: &Address) -> Result<Self, Self::Error> {
-        if addr.1 == solana_pubkey::PUBKEY_BYTES {
+        if addr.1 == AddressKind::Solana {
             let mut bytes = [0u8; 32];
-            bytes.copy_from_slice(&addr.0[..32]);
+            bytes.copy_from_slice(addr.as_bytes());
             Ok(solana_pubkey::Pubkey::new_from_array(bytes))
         } else {
             Err(Error::AddrError(format!(
-                "Invalid length for Solana address: expected {}, got {} ({addr})",
-                solana_pubkey::PUBKEY_BYTES,
+                "Invalid Solana address: expected Solana, got {:?} ({addr})",
                 addr.1
             )))
         }
@@ -119,16 +329,117 @@ impl TryFrom<&Address> for solana_pubkey::Pubkey {
 
 impl From<alloy_primitives::Address> for Address {
     fn from(addr: alloy_primitives::Address) -> Self {
-        let mut bytes = FixedBytes::<64>::ZERO;
+        let mut bytes = FixedBytes::<MAX_ADDRESS_BYTES>::ZERO;
         bytes[..20].copy_from_slice(addr.0.as_slice());
-        Self(bytes, 20)
+        Self(bytes, AddressKind::Evm)
     }
 }
 
 impl From<solana_pubkey::Pubkey> for Address {
     fn from(pubkey: solana_pubkey::Pubkey) -> Self {
-        let mut bytes = FixedBytes::<64>::ZERO;
+        let mut bytes = FixedBytes::<MAX_ADDRESS_BYTES>::ZERO;
         bytes[..solana_pubkey::PUBKEY_BYTES].copy_from_slice(&pubkey.to_bytes());
-        Self(bytes, solana_pubkey::PUBKEY_BYTES)
+        Self(bytes, AddressKind::Solana)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{ETHEREUM_USDC_CONTRACT, SOLANA_MAINNET_USDC_TOKEN},
+        rstest::rstest,
+    };
+
+    #[rstest]
+    #[case("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", AddressKind::Evm)]
+    #[case("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", AddressKind::Solana)]
+    #[case("noble1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5hpek7j", AddressKind::Noble)]
+    #[case(
+        "0x5d4b302506645c37ff133b98c4b50a5ae14841659738d6d733d59d0d217a93bf",
+        AddressKind::Other(32)
+    )]
+    #[case("0x0102", AddressKind::Other(2))]
+    fn test_parse_display(#[case] input: &str, #[case] kind: AddressKind) -> anyhow::Result<()> {
+        let address: Address = input.parse()?;
+        assert_eq!(address.kind(), kind);
+        assert_eq!(address.to_string(), input);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("0xzz")]
+    #[case("not an address")]
+    #[case("cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu")]
+    #[case("noble1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5hpek7q")]
+    #[case("0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ff")]
+    fn test_parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<Address>().is_err());
+    }
+
+    #[test]
+    fn test_with_kind() -> anyhow::Result<()> {
+        let sui: Address =
+            "0x5d4b302506645c37ff133b98c4b50a5ae14841659738d6d733d59d0d217a93bf".parse()?;
+        assert_eq!(sui.with_kind(AddressKind::Move)?.kind(), AddressKind::Move);
+        assert!(sui.with_kind(AddressKind::Evm).is_err());
+        let solana = Address::from(SOLANA_MAINNET_USDC_TOKEN);
+        assert_eq!(solana.with_kind(AddressKind::Solana)?, solana);
+        assert!(solana.with_kind(AddressKind::Move).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bytes32_roundtrip() -> anyhow::Result<()> {
+        let evm = Address::from(ETHEREUM_USDC_CONTRACT);
+        assert_eq!(evm.to_bytes32()?, ETHEREUM_USDC_CONTRACT.into_word());
+        assert_eq!(
+            Address::from_bytes32(evm.to_bytes32()?, AddressKind::Evm)?,
+            evm
+        );
+
+        let solana = Address::from(SOLANA_MAINNET_USDC_TOKEN);
+        assert_eq!(
+            solana.to_bytes32()?,
+            FixedBytes::from(SOLANA_MAINNET_USDC_TOKEN.to_bytes())
+        );
+        assert_eq!(
+            Address::from_bytes32(solana.to_bytes32()?, AddressKind::Solana)?,
+            solana
+        );
+
+        let noble: Address = "noble1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5hpek7j".parse()?;
+        let mut word = FixedBytes::<32>::ZERO;
+        word[12..].copy_from_slice(&hex::decode("0102030405060708090a0b0c0d0e0f1011121314")?);
+        assert_eq!(noble.to_bytes32()?, word);
+        assert_eq!(Address::from_bytes32(word, AddressKind::Noble)?, noble);
+
+        // the padding of an EVM address must be zero
+        assert!(Address::from_bytes32(solana.to_bytes32()?, AddressKind::Evm).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        let evm = Address::from(ETHEREUM_USDC_CONTRACT);
+        let json = serde_json::to_string(&evm)?;
+        assert_eq!(json, "\"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48\"");
+        assert_eq!(serde_json::from_str::<Address>(&json)?, evm);
+
+        #[derive(Serialize, Deserialize)]
+        struct Hex(#[serde(with = "address_hex")] Address);
+        let solana = Address::from(SOLANA_MAINNET_USDC_TOKEN);
+        let json = serde_json::to_string(&Hex(solana))?;
+        assert_eq!(
+            json,
+            format!(
+                "\"0x{}\"",
+                hex::encode(SOLANA_MAINNET_USDC_TOKEN.to_bytes())
+            )
+        );
+        assert_eq!(serde_json::from_str::<Hex>(&json)?.0, solana);
+        assert!(serde_json::from_str::<Address>("\"nope\"").is_err());
+        Ok(())
     }
 }
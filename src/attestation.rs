@@ -1,16 +1,60 @@
 use {
-    alloy_primitives::hex,
-    serde::Deserialize,
+    alloy_primitives::{FixedBytes, U256, hex},
+    serde::{Deserialize, Serialize},
     std::fmt::{Debug, Display},
 };
 
-/// To be passed to message transmitter to claim/mint
-#[derive(Clone, Eq, PartialEq)]
+/// To be passed to message transmitter to claim/mint. Serialized as `0x` hex
+/// strings
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Attestation {
+    #[serde(with = "hex::serde")]
     pub attestation: Vec<u8>,
+    #[serde(with = "hex::serde")]
     pub message: Vec<u8>,
 }
 
+/// Offset of the nonce in a CCTP v2 message, after the version and the source
+/// and destination domains
+const MESSAGE_NONCE_OFFSET: usize = 12;
+
+/// Offset of the burn message body, after the 148 bytes of the message header
+const MESSAGE_BODY_OFFSET: usize = 148;
+
+/// Offset of the burned amount in the body, after the version, burn token and
+/// mint recipient
+const BURN_AMOUNT_OFFSET: usize = MESSAGE_BODY_OFFSET + 68;
+
+/// Offset of the fee executed in the body, after the amount, message sender and
+/// max fee
+const BURN_FEE_EXECUTED_OFFSET: usize = BURN_AMOUNT_OFFSET + 96;
+
+impl Attestation {
+    /// The nonce Iris assigned to the message, `None` when the message is too
+    /// short
+    pub fn nonce(&self) -> Option<FixedBytes<32>> {
+        self.message
+            .get(MESSAGE_NONCE_OFFSET..MESSAGE_NONCE_OFFSET + 32)
+            .map(FixedBytes::from_slice)
+    }
+
+    /// The burned amount, `None` when the message is too short
+    pub fn amount(&self) -> Option<U256> {
+        self.word(BURN_AMOUNT_OFFSET)
+    }
+
+    /// The fee Iris charged the burn, `None` when the message is too short
+    pub fn fee_executed(&self) -> Option<U256> {
+        self.word(BURN_FEE_EXECUTED_OFFSET)
+    }
+
+    fn word(&self, offset: usize) -> Option<U256> {
+        self.message
+            .get(offset..offset + 32)
+            .map(U256::from_be_slice)
+    }
+}
+
 impl Debug for Attestation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -66,3 +110,54 @@ pub enum AttestationStatus {
     PendingConfirmations,
     Failed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce() {
+        let mut message = vec![0u8; 148];
+        message[MESSAGE_NONCE_OFFSET..MESSAGE_NONCE_OFFSET + 32].fill(7);
+        let attestation = Attestation {
+            attestation: vec![],
+            message,
+        };
+        assert_eq!(attestation.nonce(), Some(FixedBytes::repeat_byte(7)));
+        assert_eq!(
+            Attestation {
+                attestation: vec![],
+                message: vec![0u8; 40],
+            }
+            .nonce(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_burn_amounts() {
+        let mut message = vec![0u8; BURN_FEE_EXECUTED_OFFSET + 64];
+        message[BURN_AMOUNT_OFFSET + 31] = 100;
+        message[BURN_FEE_EXECUTED_OFFSET + 31] = 1;
+        let attestation = Attestation {
+            attestation: vec![],
+            message,
+        };
+        assert_eq!(attestation.amount(), Some(U256::from(100)));
+        assert_eq!(attestation.fee_executed(), Some(U256::from(1)));
+    }
+
+    #[test]
+    fn test_serde() {
+        let attestation = Attestation {
+            attestation: vec![1, 2],
+            message: vec![0xab],
+        };
+        let json = serde_json::to_string(&attestation).unwrap();
+        assert_eq!(json, r#"{"attestation":"0x0102","message":"0xab"}"#);
+        assert_eq!(
+            serde_json::from_str::<Attestation>(&json).unwrap(),
+            attestation
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! `cctp` moves USDC between CCTP chains from the command line.
+//!
+//! Keys and RPC URLs are read from flags or the environment, a `.env` file is
+//! loaded first like in the examples. Every command prints JSON with `--json`.
+mod output;
+mod setup;
+mod usdc;
+
+use {
+    crate::{output::*, setup::*, usdc::*},
+    alloy_chains::Chain,
+    alloy_primitives::{FixedBytes, U256, hex},
+    anyhow::{bail, format_err},
+    cctp_client::{
+        Address,
+        AddressKind,
+        Attestation,
+        AttestationResponse,
+        AttestationStatus,
+        BurnRequest,
+        Cctp,
+        CctpBuilder,
+        CctpChain,
+        CctpEndpoint,
+        SimulationMode,
+        TransferMode,
+        TransferTx,
+    },
+    clap::{Args, Parser, Subcommand, ValueEnum},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    spl_associated_token_account::get_associated_token_address,
+    std::process::ExitCode,
+    tracing::info,
+    tracing_subscriber::EnvFilter,
+};
+
+#[derive(Parser)]
+#[command(name = "cctp", version, about = "Moves USDC between CCTP chains")]
+struct Cli {
+    /// Prints results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(flatten)]
+    keys: Keys,
+    #[command(flatten)]
+    rpcs: Rpcs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// How fast a burn is attested
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+enum Mode {
+    /// Attested after soft finality, for a fee
+    Fast,
+    /// Attested after hard finality
+    #[default]
+    Standard,
+}
+
+impl From<Mode> for TransferMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Fast => Self::Fast,
+            Mode::Standard => Self::Standard,
+        }
+    }
+}
+
+#[derive(Args)]
+struct Route {
+    /// The source chain, e.g. `base-sepolia`, `solana-devnet` or a CAIP-2 id
+    #[arg(long, value_parser = parse_chain)]
+    from: Chain,
+    /// The destination chain
+    #[arg(long, value_parser = parse_chain)]
+    to: Chain,
+}
+
+#[derive(Args)]
+struct Transfer {
+    #[command(flatten)]
+    route: Route,
+    /// USDC amount, e.g. `1.5`
+    #[arg(long, value_parser = parse_usdc)]
+    amount: U256,
+    /// Account credited on the destination chain, defaults to the account of
+    /// the destination key
+    #[arg(long)]
+    recipient: Option<Address>,
+    #[arg(long, value_enum, default_value_t)]
+    mode: Mode,
+    /// Simulates the transactions of the source chain without sending any
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Burns on the source chain, waits for the attestation and receives on
+    /// the destination chain
+    Bridge(Transfer),
+    /// Burns on the source chain, for any CCTP destination
+    Burn(Transfer),
+    /// Waits for the attestation of a burn
+    Attest {
+        /// The chain of the burn
+        #[arg(long, value_parser = parse_chain)]
+        from: Chain,
+        /// The burn transaction
+        #[arg(long)]
+        tx: String,
+    },
+    /// Receives a burn on the destination chain, unless it was already
+    /// received
+    Recv {
+        #[command(flatten)]
+        route: Route,
+        /// The burn transaction
+        #[arg(long)]
+        tx: String,
+        /// The owner of the Solana token account the burn credits, defaults to
+        /// the Solana key
+        #[arg(long)]
+        recipient: Option<Address>,
+    },
+    /// Shows the attestation status of a burn, and whether it was received
+    /// when the destination is given
+    Status {
+        /// The chain of the burn
+        #[arg(long, value_parser = parse_chain)]
+        from: Chain,
+        /// The destination chain, checking it needs its RPC URL and key
+        #[arg(long, value_parser = parse_chain)]
+        to: Option<Chain>,
+        /// The burn transaction
+        #[arg(long)]
+        tx: String,
+    },
+    /// Closes the event accounts of past Solana burns, refunding their rent
+    Reclaim {
+        #[arg(long, value_parser = parse_chain, default_value = "solana")]
+        chain: Chain,
+    },
+    /// Shows the Circle fees of a route
+    Fees {
+        #[command(flatten)]
+        route: Route,
+        /// Also computes the fees of this USDC amount
+        #[arg(long, value_parser = parse_usdc)]
+        amount: Option<U256>,
+    },
+}
+
+/// Binds `$cctp` to the [`Cctp`] of `$builder` over the providers of the
+/// endpoints `$src` and `$dst`, and `$src_signer` and `$dst_signer` to their
+/// signers
+macro_rules! with_endpoints {
+    ($builder:expr, $src:expr, $dst:expr, | $cctp:ident, $src_signer:ident, $dst_signer:ident | $body:expr) => {
+        match ($src, $dst) {
+            (Endpoint::Evm(src), Endpoint::Evm(dst)) => {
+                let $cctp = $builder
+                    .with_source_provider(src.clone())
+                    .with_destination_provider(dst.clone())
+                    .build()?;
+                let ($src_signer, $dst_signer) = (&(), &());
+                $body
+            }
+            (Endpoint::Evm(src), Endpoint::Solana { rpc, keypair }) => {
+                let $cctp = $builder
+                    .with_source_provider(src.clone())
+                    .with_destination_provider(rpc.clone())
+                    .build()?;
+                let ($src_signer, $dst_signer): (&(), &dyn Signer) = (&(), keypair.as_ref());
+                $body
+            }
+            (Endpoint::Solana { rpc, keypair }, Endpoint::Evm(dst)) => {
+                let $cctp = $builder
+                    .with_source_provider(rpc.clone())
+                    .with_destination_provider(dst.clone())
+                    .build()?;
+                let ($src_signer, $dst_signer): (&dyn Signer, &()) = (keypair.as_ref(), &());
+                $body
+            }
+            (
+                Endpoint::Solana {
+                    rpc: src,
+                    keypair: src_keypair,
+                },
+                Endpoint::Solana {
+                    rpc: dst,
+                    keypair: dst_keypair,
+                },
+            ) => {
+                let $cctp = $builder
+                    .with_source_provider(src.clone())
+                    .with_destination_provider(dst.clone())
+                    .build()?;
+                let ($src_signer, $dst_signer): (&dyn Signer, &dyn Signer) =
+                    (src_keypair.as_ref(), dst_keypair.as_ref());
+                $body
+            }
+        }
+    };
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    let cli = Cli::parse();
+    match run(&cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            print_error(cli.json, &e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A builder for the route, simulating only when `dry_run`
+fn builder(route: &Route, dry_run: bool) -> CctpBuilder {
+    let simulation = if dry_run {
+        SimulationMode::DryRun
+    } else {
+        SimulationMode::default()
+    };
+    Cctp::builder()
+        .with_source_chain(route.from)
+        .with_destination_chain(route.to)
+        .with_simulation(simulation)
+}
+
+async fn run(cli: &Cli) -> anyhow::Result<()> {
+    let (keys, rpcs) = (&cli.keys, &cli.rpcs);
+    match &cli.command {
+        Command::Bridge(transfer) => {
+            let route = &transfer.route;
+            let src = endpoint(&route.from, rpcs.source_rpc.as_deref(), keys)?;
+            let dst = endpoint(&route.to, rpcs.destination_rpc.as_deref(), keys)?;
+            let recipient = transfer.recipient.unwrap_or_else(|| dst.address());
+            let report = with_endpoints!(
+                builder(route, transfer.dry_run).with_recipient(recipient),
+                &src,
+                &dst,
+                |cctp, src_signer, dst_signer| bridge(
+                    &cctp,
+                    src_signer,
+                    dst_signer,
+                    transfer.amount,
+                    transfer.mode.into()
+                )
+                .await?
+            );
+            print(cli.json, &report)
+        }
+        Command::Burn(transfer) => {
+            let route = &transfer.route;
+            let recipient = match transfer.recipient {
+                Some(recipient) => recipient,
+                None => keys.address(&route.to)?,
+            };
+            let builder = builder(route, transfer.dry_run).with_recipient(recipient);
+            let (amount, mode) = (transfer.amount, transfer.mode.into());
+            let report = match endpoint(&route.from, rpcs.source_rpc.as_deref(), keys)? {
+                Endpoint::Evm(src) => {
+                    burn(
+                        &builder.with_source_provider(src).build()?,
+                        &(),
+                        amount,
+                        mode,
+                    )
+                    .await?
+                }
+                Endpoint::Solana { rpc, keypair } => {
+                    let cctp = builder.with_source_provider(rpc).build()?;
+                    burn(&cctp, keypair.as_ref() as &dyn Signer, amount, mode).await?
+                }
+            };
+            print(cli.json, &report)
+        }
+        Command::Attest { from, tx } => {
+            let burn = parse_transfer_tx(from, tx)?;
+            let cctp = Cctp::builder().with_source_chain(*from).build()?;
+            let attestation = cctp
+                .get_attestation_with_retry(burn.to_string(), None, None)
+                .await?;
+            print(cli.json, &AttestationReport::from(&attestation))
+        }
+        Command::Recv {
+            route,
+            tx,
+            recipient,
+        } => {
+            let burn = parse_transfer_tx(&route.from, tx)?;
+            let dst = endpoint(&route.to, rpcs.destination_rpc.as_deref(), keys)?;
+            let builder =
+                builder(route, false).with_recipient(recipient.unwrap_or_else(|| dst.address()));
+            let report = match dst {
+                Endpoint::Evm(dst) => {
+                    let cctp = builder.with_destination_provider(dst).build()?;
+                    recv(&cctp, &(), burn).await?
+                }
+                Endpoint::Solana { rpc, keypair } => {
+                    let cctp = builder.with_destination_provider(rpc).build()?;
+                    recv(&cctp, keypair.as_ref() as &dyn Signer, burn).await?
+                }
+            };
+            print(cli.json, &report)
+        }
+        Command::Status { from, to, tx } => {
+            let report = status(cli, *from, *to, tx).await?;
+            print(cli.json, &report)
+        }
+        Command::Reclaim { chain } => {
+            let Endpoint::Solana { rpc, keypair } =
+                endpoint(chain, rpcs.source_rpc.as_deref(), keys)?
+            else {
+                bail!("reclaim needs a Solana chain, not {}", chain_name(chain));
+            };
+            let cctp = Cctp::builder()
+                .with_source_provider(rpc)
+                .with_source_chain(*chain)
+                .build()?;
+            let reclaimed = cctp
+                .reclaim(keypair.as_ref())
+                .await?
+                .broadcast()?
+                .into_iter()
+                .map(|(signature, account)| ReclaimedAccount {
+                    account: account.to_string(),
+                    signature: signature.to_string(),
+                })
+                .collect();
+            print(cli.json, &ReclaimReport { reclaimed })
+        }
+        Command::Fees { route, amount } => {
+            let fees = builder(route, false).build()?.get_fees().await?;
+            let fees = fees
+                .0
+                .iter()
+                .map(|fee| {
+                    let mode = [TransferMode::Fast, TransferMode::Standard]
+                        .into_iter()
+                        .find(|mode| mode.finality_threshold() == fee.finality_threshold)
+                        .map_or_else(|| "custom".to_string(), |mode| mode.to_string());
+                    ModeFee::new(
+                        mode,
+                        fee.finality_threshold,
+                        fee.min_fee,
+                        amount.map(|amount| fee.fee(amount)),
+                    )
+                })
+                .collect();
+            let report = FeesReport {
+                source: chain_name(&route.from),
+                destination: chain_name(&route.to),
+                fees,
+            };
+            print(cli.json, &report)
+        }
+    }
+}
+
+/// Quotes and bridges `amount`
+async fn bridge<S: CctpEndpoint, D: CctpEndpoint>(
+    cctp: &Cctp<S, D>,
+    source_signer: &S::Signer,
+    destination_signer: &D::Signer,
+    amount: U256,
+    mode: TransferMode,
+) -> anyhow::Result<SentReport<BridgeReport>> {
+    let quote = cctp.quote(amount, mode).await?;
+    info!("{quote}");
+    Ok(cctp
+        .bridge_request(&quote.request, source_signer, destination_signer)
+        .await?
+        .into())
+}
+
+/// Burns `amount` for the recipient of `cctp`, whose destination may have no
+/// provider
+async fn burn<S: CctpEndpoint>(
+    cctp: &Cctp<S, ()>,
+    signer: &S::Signer,
+    amount: U256,
+    mode: TransferMode,
+) -> anyhow::Result<SentReport<BurnReport>> {
+    let fee = cctp
+        .get_fees()
+        .await?
+        .for_mode(mode)
+        .ok_or_else(|| format_err!("no {mode} transfer on this route"))?
+        .fee(amount);
+    let recipient = cctp.recipient();
+    let mint_recipient = match recipient.kind() {
+        AddressKind::Solana => {
+            let owner: Pubkey = recipient.try_into()?;
+            let usdc: Pubkey = cctp.destination_chain().usdc_token_address()?.try_into()?;
+            get_associated_token_address(&owner, &usdc)
+                .to_bytes()
+                .into()
+        }
+        _ => recipient.to_bytes32()?,
+    };
+    let balance = cctp
+        .source_provider()
+        .balance(cctp.source_chain(), signer)
+        .await?;
+    if balance < amount {
+        bail!(
+            "balance {} USDC is less than {} USDC",
+            format_usdc(balance),
+            format_usdc(amount)
+        );
+    }
+    let request = BurnRequest {
+        amount,
+        destination_domain: cctp.destination_domain_id()?,
+        mint_recipient,
+        destination_caller: FixedBytes::ZERO,
+        max_fee: fee,
+        min_finality_threshold: mode.finality_threshold(),
+    };
+    let sent = S::burn(cctp, signer, &request).await?;
+    Ok(sent
+        .map(|sent| BurnReport {
+            burn: sent.tx.to_string(),
+            amount: format_usdc(amount),
+            max_fee: format_usdc(fee),
+            recipient: recipient.to_string(),
+        })
+        .into())
+}
+
+/// Attests and receives `burn` unless it was already received
+async fn recv<D: CctpEndpoint>(
+    cctp: &Cctp<(), D>,
+    signer: &D::Signer,
+    burn: TransferTx,
+) -> anyhow::Result<BridgeReport> {
+    Ok(cctp.resume_bridge(burn, signer).await?.broadcast()?.into())
+}
+
+/// Queries Iris once for `tx`, and the destination `to` once attested
+async fn status(
+    cli: &Cli,
+    from: Chain,
+    to: Option<Chain>,
+    tx: &str,
+) -> anyhow::Result<StatusReport> {
+    let burn = parse_transfer_tx(&from, tx)?;
+    let cctp = Cctp::builder().with_source_chain(from).build()?;
+    let response = cctp
+        .get_attestation(&cctp.iris_api_url(burn.to_string())?)
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(StatusReport {
+            burn: burn.to_string(),
+            status: "not_found".to_string(),
+            received: None,
+        });
+    }
+    let response: AttestationResponse = response.error_for_status()?.json().await?;
+    let message = response
+        .messages
+        .into_iter()
+        .next()
+        .ok_or_else(|| format_err!("no message for burn {burn}"))?;
+    let status = match message.status {
+        AttestationStatus::Complete => "complete",
+        AttestationStatus::Pending => "pending",
+        AttestationStatus::PendingConfirmations => "pending_confirmations",
+        AttestationStatus::Failed => "failed",
+    };
+    let received = match (to, message.status, message.attestation, message.message) {
+        (Some(to), AttestationStatus::Complete, Some(attestation), Some(message)) => {
+            let attestation = Attestation {
+                attestation: hex::decode(attestation)?,
+                message: hex::decode(message)?,
+            };
+            let received = match endpoint(&to, cli.rpcs.destination_rpc.as_deref(), &cli.keys)? {
+                Endpoint::Evm(dst) => dst.is_received(&to, &attestation).await?,
+                Endpoint::Solana { rpc, .. } => rpc.is_received(&to, &attestation).await?,
+            };
+            Some(received)
+        }
+        _ => None,
+    };
+    Ok(StatusReport {
+        burn: burn.to_string(),
+        status: status.to_string(),
+        received,
+    })
+}
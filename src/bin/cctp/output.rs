@@ -0,0 +1,236 @@
+use {
+    crate::usdc::format_usdc,
+    alloy_primitives::{U256, hex},
+    cctp_client::{Attestation, BridgeResult, Sent},
+    serde::Serialize,
+    std::fmt::{Display, Formatter},
+};
+
+/// Prints `report` as JSON when `json`, otherwise as text
+pub fn print(json: bool, report: &(impl Serialize + Display)) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+    } else {
+        println!("{report}");
+    }
+    Ok(())
+}
+
+/// Prints a failed command, as `{"error": ...}` when `json`
+pub fn print_error(json: bool, error: &anyhow::Error) {
+    if json {
+        println!("{}", serde_json::json!({ "error": format!("{error:#}") }));
+    } else {
+        eprintln!("error: {error:#}");
+    }
+}
+
+#[derive(Serialize)]
+pub struct AttestationReport {
+    pub message: String,
+    pub attestation: String,
+    /// The nonce Iris assigned to the message
+    pub nonce: Option<String>,
+}
+
+impl From<&Attestation> for AttestationReport {
+    fn from(attestation: &Attestation) -> Self {
+        Self {
+            message: hex::encode_prefixed(&attestation.message),
+            attestation: hex::encode_prefixed(&attestation.attestation),
+            nonce: attestation.nonce().map(|nonce| nonce.to_string()),
+        }
+    }
+}
+
+impl Display for AttestationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "message: {}", self.message)?;
+        writeln!(f, "attestation: {}", self.attestation)?;
+        write!(f, "nonce: {}", self.nonce.as_deref().unwrap_or("unknown"))
+    }
+}
+
+/// Outcome of a command that sends transactions, or the simulations of its
+/// `--dry-run`
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SentReport<T> {
+    Broadcast(T),
+    Simulated { simulations: Vec<String> },
+}
+
+impl<T, R: From<T>> From<Sent<T>> for SentReport<R> {
+    fn from(sent: Sent<T>) -> Self {
+        match sent {
+            Sent::Broadcast(sent) => Self::Broadcast(sent.into()),
+            Sent::Simulated(simulations) => Self::Simulated {
+                simulations: simulations.iter().map(ToString::to_string).collect(),
+            },
+        }
+    }
+}
+
+impl<T: Display> Display for SentReport<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Broadcast(report) => write!(f, "{report}"),
+            Self::Simulated { simulations } => {
+                write!(f, "dry run, nothing was sent")?;
+                for simulation in simulations {
+                    write!(
+                        f,
+                        "
+simulated {simulation}"
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Outcome of `bridge` and `recv`
+#[derive(Serialize)]
+pub struct BridgeReport {
+    pub burn: String,
+    /// `None` when the message was already received
+    pub receive: Option<String>,
+    pub attestation: AttestationReport,
+    pub simulations: Vec<String>,
+}
+
+impl<B: Display, R: Display> From<BridgeResult<B, R>> for BridgeReport {
+    fn from(result: BridgeResult<B, R>) -> Self {
+        Self {
+            burn: result.burn.to_string(),
+            receive: result.recv.map(|recv| recv.to_string()),
+            attestation: (&result.attestation).into(),
+            simulations: result.simulations.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl Display for BridgeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "burn: {}", self.burn)?;
+        writeln!(
+            f,
+            "receive: {}",
+            self.receive.as_deref().unwrap_or("already received")
+        )?;
+        write!(f, "{}", self.attestation)
+    }
+}
+
+/// Outcome of `burn`
+#[derive(Serialize)]
+pub struct BurnReport {
+    pub burn: String,
+    pub amount: String,
+    pub max_fee: String,
+    pub recipient: String,
+}
+
+impl Display for BurnReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "burned {} USDC (max fee {}) for {} in {}",
+            self.amount, self.max_fee, self.recipient, self.burn
+        )
+    }
+}
+
+/// Outcome of `status`
+#[derive(Serialize)]
+pub struct StatusReport {
+    pub burn: String,
+    /// `not_found`, `pending`, `pending_confirmations`, `complete` or
+    /// `failed`
+    pub status: String,
+    /// Whether the destination received the message, when checked
+    pub received: Option<bool>,
+}
+
+impl Display for StatusReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "burn {}: {}", self.burn, self.status)?;
+        match self.received {
+            Some(true) => write!(f, ", received"),
+            Some(false) => write!(f, ", not received"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReclaimedAccount {
+    pub account: String,
+    pub signature: String,
+}
+
+/// Outcome of `reclaim`
+#[derive(Serialize)]
+pub struct ReclaimReport {
+    pub reclaimed: Vec<ReclaimedAccount>,
+}
+
+impl Display for ReclaimReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "reclaimed {} accounts", self.reclaimed.len())?;
+        for reclaimed in &self.reclaimed {
+            write!(
+                f,
+                "\nreclaimed account {} with signature {}",
+                reclaimed.account, reclaimed.signature
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct ModeFee {
+    pub mode: String,
+    pub finality_threshold: u32,
+    pub fee_bps: u32,
+    /// The fee of the requested amount, in USDC
+    pub fee: Option<String>,
+}
+
+/// Outcome of `fees`
+#[derive(Serialize)]
+pub struct FeesReport {
+    pub source: String,
+    pub destination: String,
+    pub fees: Vec<ModeFee>,
+}
+
+impl ModeFee {
+    pub fn new(mode: String, finality_threshold: u32, fee_bps: u32, fee: Option<U256>) -> Self {
+        Self {
+            mode,
+            finality_threshold,
+            fee_bps,
+            fee: fee.map(format_usdc),
+        }
+    }
+}
+
+impl Display for FeesReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.source, self.destination)?;
+        for fee in &self.fees {
+            write!(
+                f,
+                "\n{}: finality threshold {}, {} bps",
+                fee.mode, fee.finality_threshold, fee.fee_bps
+            )?;
+            if let Some(amount) = &fee.fee {
+                write!(f, ", {amount} USDC")?;
+            }
+        }
+        Ok(())
+    }
+}
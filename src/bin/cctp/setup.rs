@@ -0,0 +1,275 @@
+use {
+    alloy_chains::{Chain, NamedChain},
+    alloy_network::Ethereum,
+    alloy_provider::{Provider, ProviderBuilder, WalletProvider},
+    alloy_signer_local::PrivateKeySigner,
+    anyhow::{Context, bail, format_err},
+    cctp_client::{
+        APTOS,
+        APTOS_TESTNET,
+        Address,
+        AddressKind,
+        Caip2,
+        CctpChain,
+        NOBLE,
+        NOBLE_TESTNET,
+        SOLANA_DEVNET,
+        SOLANA_MAINNET,
+        SUI,
+        SUI_TESTNET,
+        SolanaWrapper,
+        TransferTx,
+    },
+    clap::Args,
+    solana_commitment_config::CommitmentConfig,
+    solana_keypair::Keypair,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signer::Signer,
+    std::{env, fs, path::PathBuf, str::FromStr, sync::Arc},
+    tracing::info,
+};
+
+/// Names of the crate's non EVM chains, EVM chains use their
+/// [`NamedChain`] name
+const NON_EVM_CHAINS: &[(&str, Chain)] = &[
+    ("solana", SOLANA_MAINNET),
+    ("solana-devnet", SOLANA_DEVNET),
+    ("noble", NOBLE),
+    ("noble-testnet", NOBLE_TESTNET),
+    ("sui", SUI),
+    ("sui-testnet", SUI_TESTNET),
+    ("aptos", APTOS),
+    ("aptos-testnet", APTOS_TESTNET),
+];
+
+/// Parses a supported chain from its name, e.g. `base-sepolia` or
+/// `solana-devnet`, its CAIP-2 id or its EVM chain id
+pub fn parse_chain(s: &str) -> anyhow::Result<Chain> {
+    let chain = match NON_EVM_CHAINS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+    {
+        Some((_, chain)) => *chain,
+        None if s.contains(':') => Caip2::from_str(s)?.chain(),
+        None => match NamedChain::from_str(s) {
+            Ok(named) => named.into(),
+            Err(_) => Chain::from_id(s.parse().with_context(|| format!("unknown chain {s}"))?),
+        },
+    };
+    if !chain.is_supported() {
+        bail!("{s} is not a supported CCTP chain");
+    }
+    Ok(chain)
+}
+
+/// The name [`parse_chain`] accepts for `chain`
+pub fn chain_name(chain: &Chain) -> String {
+    NON_EVM_CHAINS
+        .iter()
+        .find(|(_, known)| known == chain)
+        .map_or_else(|| chain.to_string(), |(name, _)| name.to_string())
+}
+
+/// The kind of the accounts of `chain`
+pub fn address_kind(chain: &Chain) -> AddressKind {
+    Caip2::from(*chain).address_kind()
+}
+
+/// Parses the burn transaction `tx` sent on `chain`
+pub fn parse_transfer_tx(chain: &Chain, tx: &str) -> anyhow::Result<TransferTx> {
+    match address_kind(chain) {
+        AddressKind::Evm => Ok(TransferTx::Evm(tx.parse()?)),
+        AddressKind::Solana => Ok(TransferTx::Solana(tx.parse()?)),
+        _ => bail!("{} burns are not supported", chain_name(chain)),
+    }
+}
+
+/// Keys signing transactions, read like the examples do
+#[derive(Args, Debug, Default)]
+pub struct Keys {
+    /// Hex private key of EVM transactions
+    #[arg(long, env = "EVM_SECRET_KEY", hide_env_values = true, global = true)]
+    pub evm_key: Option<String>,
+    /// File holding the hex private key of EVM transactions, preferred over
+    /// --evm-key
+    #[arg(long, env = "EVM_KEY_FILE", global = true)]
+    pub evm_key_file: Option<PathBuf>,
+    /// Solana keypair file
+    #[arg(long, env = "KEYPAIR_FILE", global = true)]
+    pub keypair: Option<PathBuf>,
+    /// Base58 Solana secret key, used without a keypair file
+    #[arg(long, env = "TEST_PRIVATE_KEY", hide_env_values = true, global = true)]
+    pub solana_key: Option<String>,
+}
+
+impl Keys {
+    fn evm_signer(&self) -> anyhow::Result<PrivateKeySigner> {
+        let key = match (&self.evm_key_file, &self.evm_key) {
+            (Some(file), _) => fs::read_to_string(file)
+                .with_context(|| format!("unable to read {}", file.display()))?,
+            (None, Some(key)) => key.clone(),
+            (None, None) => bail!("no EVM key, pass --evm-key-file or set EVM_SECRET_KEY"),
+        };
+        PrivateKeySigner::from_str(key.trim()).context("invalid EVM private key")
+    }
+
+    /// The account of the key signing on `chain`
+    pub fn address(&self, chain: &Chain) -> anyhow::Result<Address> {
+        match address_kind(chain) {
+            AddressKind::Evm => Ok(self.evm_signer()?.address().into()),
+            AddressKind::Solana => Ok(self.solana_keypair()?.pubkey().into()),
+            _ => bail!("no key for {}, pass --recipient", chain_name(chain)),
+        }
+    }
+
+    fn solana_keypair(&self) -> anyhow::Result<Keypair> {
+        match (&self.keypair, &self.solana_key) {
+            (Some(file), _) => solana_keypair::read_keypair_file(file)
+                .map_err(|e| format_err!("unable to load keypair file {} {e}", file.display())),
+            (None, Some(key)) => {
+                let bytes = bs58::decode(key)
+                    .into_vec()
+                    .map_err(|e| format_err!("invalid base58 Solana key: {e}"))?;
+                Keypair::try_from(bytes.as_slice())
+                    .map_err(|e| format_err!("invalid Solana key: {e}"))
+            }
+            (None, None) => bail!("no Solana key, pass --keypair or set KEYPAIR_FILE"),
+        }
+    }
+}
+
+/// RPC endpoints of the source and destination chains
+#[derive(Args, Debug)]
+pub struct Rpcs {
+    /// RPC URL of the source chain, defaults to $CCTP_RPC_URL_<chain id>
+    #[arg(long, global = true)]
+    pub source_rpc: Option<String>,
+    /// RPC URL of the destination chain, defaults to $CCTP_RPC_URL_<chain id>
+    #[arg(long, global = true)]
+    pub destination_rpc: Option<String>,
+}
+
+/// The RPC URL of `chain`, `url` when given, otherwise from the environment.
+/// Solana falls back to $SOLANA_RPC_URL like the examples
+fn rpc_url(chain: &Chain, url: Option<&str>) -> anyhow::Result<String> {
+    if let Some(url) = url {
+        return Ok(url.to_string());
+    }
+    let var = format!("CCTP_RPC_URL_{}", chain.id());
+    env::var(&var)
+        .or_else(|e| match address_kind(chain) {
+            AddressKind::Solana => env::var("SOLANA_RPC_URL"),
+            _ => Err(e),
+        })
+        .map_err(|_| format_err!("no RPC URL for {}, set {var}", chain_name(chain)))
+}
+
+/// The provider and signer of one chain
+pub enum Endpoint<P> {
+    /// Transactions are signed by the provider's wallet
+    Evm(P),
+    Solana {
+        rpc: SolanaWrapper,
+        keypair: Arc<Keypair>,
+    },
+}
+
+impl<P: WalletProvider> Endpoint<P> {
+    /// The account of the signer
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Evm(provider) => provider.default_signer_address().into(),
+            Self::Solana { keypair, .. } => keypair.pubkey().into(),
+        }
+    }
+}
+
+/// Connects to `chain` with the keys of its chain family
+pub fn endpoint(
+    chain: &Chain,
+    url: Option<&str>,
+    keys: &Keys,
+) -> anyhow::Result<Endpoint<impl Provider<Ethereum> + WalletProvider + Clone>> {
+    match address_kind(chain) {
+        AddressKind::Evm => {
+            let url = rpc_url(chain, url)?;
+            let wallet = keys.evm_signer()?;
+            info!("evm address {} on {}", wallet.address(), chain_name(chain));
+            let provider = ProviderBuilder::new()
+                .wallet(wallet)
+                .connect_http(url.parse()?);
+            Ok(Endpoint::Evm(provider))
+        }
+        AddressKind::Solana => {
+            let url = rpc_url(chain, url)?;
+            let keypair = keys.solana_keypair()?;
+            info!("solana address {} using RPC {url}", keypair.pubkey());
+            let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::finalized());
+            Ok(Endpoint::Solana {
+                rpc: rpc.into(),
+                keypair: Arc::new(keypair),
+            })
+        }
+        _ => bail!(
+            "{} has no provider, only burns to it are supported",
+            chain_name(chain)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    #[rstest]
+    #[case("base-sepolia", NamedChain::BaseSepolia.into())]
+    #[case("8453", NamedChain::Base.into())]
+    #[case("eip155:1", NamedChain::Mainnet.into())]
+    #[case("solana-devnet", SOLANA_DEVNET)]
+    #[case("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp", SOLANA_MAINNET)]
+    fn test_parse_chain(#[case] input: &str, #[case] expected: Chain) {
+        let chain = parse_chain(input).unwrap();
+        assert_eq!(chain, expected);
+        assert_eq!(parse_chain(&chain_name(&chain)).unwrap(), chain);
+    }
+
+    #[rstest]
+    #[case("bsc")]
+    #[case("42")]
+    #[case("not-a-chain")]
+    fn test_parse_chain_unsupported(#[case] input: &str) {
+        assert!(parse_chain(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_transfer_tx() {
+        let hash = alloy_primitives::TxHash::repeat_byte(1);
+        assert_eq!(
+            parse_transfer_tx(&NamedChain::Base.into(), &hash.to_string()).unwrap(),
+            TransferTx::Evm(hash)
+        );
+        assert!(parse_transfer_tx(&SOLANA_MAINNET, &hash.to_string()).is_err());
+        assert!(parse_transfer_tx(&NOBLE, &hash.to_string()).is_err());
+    }
+
+    #[rstest]
+    #[case("not base58 0OIl")]
+    #[case("3yZe7d")]
+    fn test_solana_keypair_invalid(#[case] key: &str) {
+        let keys = Keys {
+            solana_key: Some(key.to_string()),
+            ..Default::default()
+        };
+        assert!(keys.solana_keypair().is_err());
+    }
+
+    #[test]
+    fn test_solana_keypair() {
+        let keypair = Keypair::new();
+        let keys = Keys {
+            solana_key: Some(keypair.to_base58_string()),
+            ..Default::default()
+        };
+        assert_eq!(keys.solana_keypair().unwrap().pubkey(), keypair.pubkey());
+    }
+}
@@ -0,0 +1,76 @@
+use {alloy_primitives::U256, anyhow::bail};
+
+/// USDC has 6 decimals on every CCTP domain
+pub const USDC_DECIMALS: usize = 6;
+
+/// Parses a human USDC amount, e.g. `1.5`, into base units
+pub fn parse_usdc(s: &str) -> anyhow::Result<U256> {
+    let s = s.trim();
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        bail!("empty USDC amount");
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        bail!("invalid USDC amount {s}");
+    }
+    if fraction.len() > USDC_DECIMALS {
+        bail!("USDC amount {s} has more than {USDC_DECIMALS} decimals");
+    }
+    let digits = format!("{whole}{fraction:0<USDC_DECIMALS$}");
+    let amount = U256::from_str_radix(&digits, 10)?;
+    if amount.is_zero() {
+        bail!("USDC amount must be positive");
+    }
+    Ok(amount)
+}
+
+/// Formats USDC base units as a human amount, e.g. `1.5`
+pub fn format_usdc(amount: U256) -> String {
+    let digits = format!("{:0>width$}", amount.to_string(), width = USDC_DECIMALS + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - USDC_DECIMALS);
+    match fraction.trim_end_matches('0') {
+        "" => whole.to_string(),
+        fraction => format!("{whole}.{fraction}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    #[rstest]
+    #[case("1", 1_000_000)]
+    #[case("1.5", 1_500_000)]
+    #[case("0.000001", 1)]
+    #[case(".25", 250_000)]
+    #[case("10.", 10_000_000)]
+    fn test_parse_usdc(#[case] input: &str, #[case] expected: u64) {
+        assert_eq!(parse_usdc(input).unwrap(), U256::from(expected));
+        assert_eq!(
+            parse_usdc(&format_usdc(U256::from(expected))).unwrap(),
+            U256::from(expected)
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("0")]
+    #[case("1.0000001")]
+    #[case("-1")]
+    #[case("1e6")]
+    fn test_parse_usdc_invalid(#[case] input: &str) {
+        assert!(parse_usdc(input).is_err());
+    }
+
+    #[test]
+    fn test_format_usdc() {
+        assert_eq!(format_usdc(U256::ZERO), "0");
+        assert_eq!(format_usdc(U256::from(1)), "0.000001");
+        assert_eq!(format_usdc(U256::from(1_500_000)), "1.5");
+        assert_eq!(format_usdc(U256::from(20_000_000)), "20");
+    }
+}
@@ -6,9 +6,10 @@ use {
         AttestationResponse,
         AttestationStatus,
         CctpChain,
+        Domain,
         error::{Error, Result},
     },
-    alloy_chains::{Chain, ChainKind, NamedChain},
+    alloy_chains::Chain,
     alloy_network::Ethereum,
     alloy_primitives::{
         FixedBytes,
@@ -17,9 +18,12 @@ use {
     },
     alloy_provider::Provider,
     alloy_sol_types::SolEvent,
+    receipt::display_fromstr,
     reqwest::{Client, Response},
+    serde::{Deserialize, Serialize},
     solana_signature::Signature as SolanaSignature,
     std::{
+        collections::HashMap,
         fmt::{Debug, Display},
         time::Duration,
     },
@@ -27,53 +31,116 @@ use {
     tracing::{Level, debug, error, info, instrument, trace},
 };
 
+mod approval;
+mod builder;
+mod confirmation;
+mod endpoint;
 mod evm;
 mod fee;
+mod gas;
+mod hub;
+mod prepare;
+mod quote;
+mod receipt;
 mod reclaim;
 mod recv;
+mod safe;
+mod simulation;
 mod solana;
-
-pub use {fee::*, solana::*};
+mod solana_tx;
+mod token_account;
+mod user_operation;
+
+pub use {
+    approval::*,
+    builder::*,
+    confirmation::*,
+    endpoint::*,
+    fee::*,
+    gas::*,
+    hub::*,
+    prepare::*,
+    quote::*,
+    receipt::*,
+    simulation::*,
+    solana::*,
+    solana_tx::*,
+    token_account::*,
+    user_operation::*,
+};
 /// Circle Iris API environment URLs
 ///
 /// See <https://developers.circle.com/stablecoins/cctp-apis>
 pub const IRIS_API: &str = "https://iris-api.circle.com";
 pub const IRIS_API_SANDBOX: &str = "https://iris-api-sandbox.circle.com";
 
-/// Default confirmation requirements and timeouts for different chains
+/// The Iris API environment a [`Cctp`] attests through
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrisEnvironment {
+    Mainnet,
+    Sandbox,
+}
+
+impl IrisEnvironment {
+    /// The environment attesting burns on `chain`
+    pub fn of_chain(chain: &Chain) -> Self {
+        if chain.sandbox() {
+            Self::Sandbox
+        } else {
+            Self::Mainnet
+        }
+    }
+
+    pub const fn url(&self) -> &'static str {
+        match self {
+            Self::Mainnet => IRIS_API,
+            Self::Sandbox => IRIS_API_SANDBOX,
+        }
+    }
+}
+
+/// Confirmation timeout for chains without one in the
+/// [`ChainRegistry`](crate::ChainRegistry)
 pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(180); // 3 minutes default
-pub const CHAIN_CONFIRMATION_CONFIG: &[(NamedChain, u64, Duration)] = &[
-    // (Chain, Required Confirmations, Timeout)
-    (NamedChain::Mainnet, 2, Duration::from_secs(300)), // 5 mins for Ethereum
-    (NamedChain::Arbitrum, 1, Duration::from_secs(120)), // 2 mins for Arbitrum
-    (NamedChain::Optimism, 1, Duration::from_secs(120)), // 2 mins for Optimism
-    (NamedChain::Polygon, 15, Duration::from_secs(180)), // More confirmations for Polygon
-    (NamedChain::Avalanche, 3, Duration::from_secs(120)), // 2 mins for Avalanche
-    (NamedChain::BinanceSmartChain, 2, Duration::from_secs(120)), // 2 mins for BNB Chain
-    (NamedChain::Base, 1, Duration::from_secs(120)),    // 2 mins for Base
-    (NamedChain::Unichain, 1, Duration::from_secs(120)), // 2 mins for Unichain
-];
-
-/// Gets the chain-specific confirmation configuration
+
+/// Gets the chain-specific confirmation configuration from the
+/// [`ChainRegistry`](crate::ChainRegistry)
 pub fn get_chain_confirmation_config(chain: &Chain) -> (u64, Duration) {
-    match chain.kind() {
-        ChainKind::Named(n) => CHAIN_CONFIRMATION_CONFIG
-            .iter()
-            .find(|(ch, _, _)| ch == n)
-            .map(|(_, confirmations, timeout)| (*confirmations, *timeout))
-            .unwrap_or((1, DEFAULT_CONFIRMATION_TIMEOUT)),
-        ChainKind::Id(_) => (2, Duration::from_secs(4)), // TODO add specific timeout for id chain
+    crate::registry::chain_config(chain)
+        .map(|config| config.confirmation_config())
+        .unwrap_or((1, DEFAULT_CONFIRMATION_TIMEOUT))
+}
+
+/// How a [`Cctp`] polls Iris for the attestation of a burn, 30 minutes every
+/// 10 seconds by default
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttestationConfig {
+    /// Polls before failing with [`Error::AttestationTimeout`]
+    pub max_attempts: u32,
+    /// Seconds between polls
+    pub poll_interval: u64,
+}
+
+impl Default for AttestationConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 180,
+            poll_interval: 10,
+        }
     }
 }
 
 /// For solana reclaim accounts
 // pub fn dummy_provider()
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SolanaEvmBridgeResult {
+    #[serde(with = "display_fromstr")]
     pub burn: SolanaSignature,
     pub recv: TxHash,
     pub attestation: Attestation,
+    /// Simulations of the burn and receive, empty when disabled
+    pub simulations: Vec<Simulation>,
 }
 
 impl Display for SolanaEvmBridgeResult {
@@ -86,36 +153,42 @@ impl Display for SolanaEvmBridgeResult {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EvmSolanaBridgeResult {
+    pub approval: Approval,
     pub burn: TxHash,
+    #[serde(with = "display_fromstr")]
     pub recv: SolanaSignature,
     pub attestation: Attestation,
+    /// Simulations of the burn and receive, empty when disabled
+    pub simulations: Vec<Simulation>,
 }
 
 impl Display for EvmSolanaBridgeResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Burn: {}, Receive: {}, Attestation: {}",
-            self.burn, self.recv, self.attestation
+            "Approval: {}, Burn: {}, Receive: {}, Attestation: {}",
+            self.approval, self.burn, self.recv, self.attestation
         )
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EvmBridgeResult {
-    pub approval: Option<TxHash>,
+    pub approval: Approval,
     pub burn: TxHash,
     pub recv: TxHash,
     pub attestation: crate::Attestation,
+    /// Simulations of the approval, burn and receive, empty when disabled
+    pub simulations: Vec<Simulation>,
 }
 
 impl Display for EvmBridgeResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Approval: {:?}, Burn: {}, Receive: {}, Attestation: {}",
+            "Approval: {}, Burn: {}, Receive: {}, Attestation: {}",
             self.approval, self.burn, self.recv, self.attestation
         )
     }
@@ -129,12 +202,25 @@ pub struct Cctp<SrcProvider, DstProvider> {
     destination_chain: Chain,
     recipient: Address,
     client: Client,
+    approval_strategy: ApprovalStrategy,
+    gas_policies: HashMap<Chain, GasPolicy>,
+    solana_tx_config: SolanaTxConfig,
+    attestation_config: AttestationConfig,
+    confirmations: HashMap<(Chain, Option<Operation>), (ConfirmationPolicy, Duration)>,
+    iris_environment: Option<IrisEnvironment>,
+    simulation: SimulationMode,
 }
 
 impl<SrcProvider, DstProvider> Debug for Cctp<SrcProvider, DstProvider> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let src_domain = self.source_chain.cctp_domain_id().unwrap_or(u32::MAX);
-        let dst_domain = self.destination_chain.cctp_domain_id().unwrap_or(u32::MAX);
+        let src_domain = self
+            .source_chain
+            .cctp_domain_id()
+            .map_or(u32::MAX, Domain::id);
+        let dst_domain = self
+            .destination_chain
+            .cctp_domain_id()
+            .map_or(u32::MAX, Domain::id);
         write!(
             f,
             "CCTP[{}({})->{}({})]",
@@ -146,11 +232,9 @@ impl<SrcProvider, DstProvider> Debug for Cctp<SrcProvider, DstProvider> {
 impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
     /// Returns the CCTP API URL for the current environment
     pub fn api_url(&self) -> &'static str {
-        if self.source_chain.sandbox() {
-            IRIS_API_SANDBOX
-        } else {
-            IRIS_API
-        }
+        self.iris_environment
+            .unwrap_or_else(|| IrisEnvironment::of_chain(&self.source_chain))
+            .url()
     }
 
     /// Returns the source chain
@@ -163,8 +247,13 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
         &self.destination_chain
     }
 
-    /// Returns the destination domain id
-    pub fn destination_domain_id(&self) -> Result<u32> {
+    /// Returns the source domain
+    pub fn source_domain_id(&self) -> Result<Domain> {
+        self.source_chain.cctp_domain_id()
+    }
+
+    /// Returns the destination domain
+    pub fn destination_domain_id(&self) -> Result<Domain> {
         self.destination_chain.cctp_domain_id()
     }
 
@@ -195,6 +284,28 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
         &self.recipient
     }
 
+    /// Returns the [`ApprovalStrategy`] used by EVM burns
+    pub fn approval_strategy(&self) -> ApprovalStrategy {
+        self.approval_strategy
+    }
+
+    /// Sets the [`ApprovalStrategy`] used by EVM burns
+    pub fn with_approval_strategy(mut self, approval_strategy: ApprovalStrategy) -> Self {
+        self.approval_strategy = approval_strategy;
+        self
+    }
+
+    /// Returns the [`AttestationConfig`] used when polling Iris
+    pub fn attestation_config(&self) -> AttestationConfig {
+        self.attestation_config
+    }
+
+    /// Sets the [`AttestationConfig`] used when polling Iris
+    pub fn with_attestation_config(mut self, attestation_config: AttestationConfig) -> Self {
+        self.attestation_config = attestation_config;
+        self
+    }
+
     /// Constructs the Iris API URL for a given message hash
     ///
     /// # Arguments
@@ -203,16 +314,15 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
     ///
     /// # Returns
     ///
-    /// The full URL to query the attestation status
-    pub fn iris_api_url(&self, message_hash: impl AsRef<str>) -> String {
-        format!(
+    /// The full URL to query the attestation status, or
+    /// [`Error::ChainNotSupported`] when the source chain has no CCTP domain
+    pub fn iris_api_url(&self, message_hash: impl AsRef<str>) -> Result<String> {
+        Ok(format!(
             "{}/v2/messages/{}?transactionHash={}",
             self.api_url(),
-            self.source_chain()
-                .cctp_domain_id()
-                .expect("Chain is not supported"),
+            self.source_chain().cctp_domain_id()?.id(),
             message_hash.as_ref()
-        )
+        ))
     }
 
     /// Wrapper call to [`get_attestation_with_retry`] for evm [`TxHash`]
@@ -235,9 +345,10 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
     /// # Arguments
     ///
     /// * `message_hash`: The hash of the message to get the attestation for
-    /// * `max_attempts`: Maximum number of polling attempts (default: 30)
+    /// * `max_attempts`: Maximum number of polling attempts (default:
+    ///   [`AttestationConfig::max_attempts`])
     /// * `poll_interval`: Time to wait between polling attempts in seconds
-    ///   (default: 60)
+    ///   (default: [`AttestationConfig::poll_interval`])
     ///
     /// # Returns
     ///
@@ -248,12 +359,12 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
         max_attempts: Option<u32>,
         poll_interval: Option<u64>,
     ) -> Result<Attestation> {
-        let max_attempts = max_attempts.unwrap_or(30);
-        let poll_interval = poll_interval.unwrap_or(60);
+        let max_attempts = max_attempts.unwrap_or(self.attestation_config.max_attempts);
+        let poll_interval = poll_interval.unwrap_or(self.attestation_config.poll_interval);
 
         info!(message_hash = ?message_hash.as_ref(), "Polling for attestation ...");
 
-        let url = self.iris_api_url(message_hash);
+        let url = self.iris_api_url(message_hash)?;
 
         info!(url = ?url, "Attestation URL");
 
@@ -392,40 +503,45 @@ impl<SrcProvider: Provider<Ethereum> + Clone, DstProvider> Cctp<SrcProvider, Dst
         &self,
         tx_hash: TxHash,
     ) -> Result<(Vec<u8>, FixedBytes<32>)> {
-        let tx_receipt = self
-            .source_provider
-            .get_transaction_receipt(tx_hash)
-            .await?;
-
-        if let Some(tx_receipt) = tx_receipt {
-            // Calculate the event topic by hashing the event signature
-            let message_sent_topic = alloy_primitives::keccak256(b"MessageSent(bytes)");
-
-            let message_sent_log = tx_receipt
-                .inner
-                .logs()
-                .iter()
-                .find(|log| {
-                    log.topics()
-                        .first()
-                        .is_some_and(|topic| topic.as_slice() == message_sent_topic)
-                })
-                .ok_or_else(|| Error::TransactionFailed {
-                    reason: "MessageSent event not found".to_string(),
-                })?;
-
-            // Decode the log data using the generated event bindings
-            let decoded = MessageSent::abi_decode_data(&message_sent_log.data().data)?;
-
-            let message_sent_event = decoded.0.to_vec();
-            let message_hash = alloy_primitives::keccak256(&message_sent_event);
-
-            Ok((message_sent_event, message_hash))
-        } else {
-            return Err(Error::TransactionFailed {
-                reason: "Transaction not found".to_string(),
-            });
-        }
+        message_sent_event(&self.source_provider, tx_hash).await
+    }
+}
+
+/// The `MessageSent` message of the transaction `tx_hash` and its hash
+pub(crate) async fn message_sent_event<P: Provider<Ethereum>>(
+    provider: &P,
+    tx_hash: TxHash,
+) -> Result<(Vec<u8>, FixedBytes<32>)> {
+    let tx_receipt = provider.get_transaction_receipt(tx_hash).await?;
+
+    if let Some(tx_receipt) = tx_receipt {
+        // Calculate the event topic by hashing the event signature
+        let message_sent_topic = alloy_primitives::keccak256(b"MessageSent(bytes)");
+
+        let message_sent_log = tx_receipt
+            .inner
+            .logs()
+            .iter()
+            .find(|log| {
+                log.topics()
+                    .first()
+                    .is_some_and(|topic| topic.as_slice() == message_sent_topic)
+            })
+            .ok_or_else(|| Error::TransactionFailed {
+                reason: "MessageSent event not found".to_string(),
+            })?;
+
+        // Decode the log data using the generated event bindings
+        let decoded = MessageSent::abi_decode_data(&message_sent_log.data().data)?;
+
+        let message_sent_event = decoded.0.to_vec();
+        let message_hash = alloy_primitives::keccak256(&message_sent_event);
+
+        Ok((message_sent_event, message_hash))
+    } else {
+        Err(Error::TransactionFailed {
+            reason: "Transaction not found".to_string(),
+        })
     }
 }
 
@@ -459,4 +575,27 @@ mod tests {
             Error::ChainNotSupported { .. }
         ));
     }
+
+    #[test]
+    fn test_solana_evm_bridge_result_serde() {
+        let result = SolanaEvmBridgeResult {
+            burn: SolanaSignature::from([1; 64]),
+            recv: TxHash::repeat_byte(2),
+            attestation: Attestation {
+                attestation: vec![3],
+                message: vec![4],
+            },
+            simulations: vec![Simulation {
+                operation: Operation::Burn,
+                cost: SimulatedCost::ComputeUnits(50_000),
+            }],
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["burn"], result.burn.to_string());
+        assert_eq!(json["attestation"]["message"], "0x04");
+        assert_eq!(json["simulations"][0]["operation"], "burn");
+        let decoded: SolanaEvmBridgeResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.burn, result.burn);
+        assert_eq!(decoded.simulations, result.simulations);
+    }
 }
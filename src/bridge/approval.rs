@@ -0,0 +1,243 @@
+use {
+    super::{Cctp, Operation, Sent, gas::send_with_gas_policy},
+    crate::{CctpChain, ERC20, Error, Result, TokenMessengerContract},
+    alloy_network::Ethereum,
+    alloy_primitives::{Address as EvmAddress, B256, FixedBytes, TxHash, U256, keccak256},
+    alloy_provider::{Provider, WalletProvider},
+    alloy_rpc_types::{
+        TransactionRequest,
+        state::{StateOverride, StateOverridesBuilder},
+    },
+    serde::{Deserialize, Serialize},
+    std::fmt::{Display, Formatter},
+    tracing::{debug, info},
+};
+
+/// Storage slot of the `allowed` mapping of the USDC `FiatToken` contracts
+const USDC_ALLOWED_SLOT: u64 = 10;
+
+/// Overrides the USDC allowance of `spender` from `owner`, to simulate a burn
+/// whose approval was only simulated
+pub(crate) fn allowance_override(
+    usdc: EvmAddress,
+    owner: EvmAddress,
+    spender: EvmAddress,
+    allowance: U256,
+) -> StateOverride {
+    // allowed[owner][spender] of `mapping(address => mapping(address => uint256))`
+    let slot = U256::from(USDC_ALLOWED_SLOT).to_be_bytes::<32>();
+    let owner_slot = keccak256([owner.into_word().0, slot].concat());
+    let slot = keccak256([spender.into_word().0, owner_slot.0].concat());
+    StateOverridesBuilder::default()
+        .with_state_diff(usdc, [(slot, B256::from(allowance))])
+        .build()
+}
+
+/// How the `TokenMessenger` is allowed to spend USDC before an EVM burn
+///
+/// There is no signed permit variant: `depositForBurn` burns from
+/// `msg.sender`, so an EIP-2612 or Permit2 signature could only replace the
+/// approval through a router that redeems it and burns in one transaction.
+/// Shared contracts like Multicall3 would let anyone front-run the signature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStrategy {
+    /// Approve exactly the burn amount when the allowance is short
+    #[default]
+    Exact,
+    /// Approve a fixed cap when the allowance is short, the cap must cover the
+    /// burn amount
+    Cap(U256),
+    /// Approve `U256::MAX` when the allowance is short
+    Unlimited,
+    /// Never approve, the existing allowance must cover the burn amount
+    PreApproved,
+}
+
+impl ApprovalStrategy {
+    /// The allowance to approve for a burn of `amount`, or `None` if this
+    /// strategy never sends an approval
+    pub fn target_allowance(&self, amount: U256) -> Result<Option<U256>> {
+        match self {
+            Self::Exact => Ok(Some(amount)),
+            Self::Cap(cap) if *cap < amount => Err(Error::InvalidConfig(format!(
+                "approval cap {cap} is below burn amount {amount}"
+            ))),
+            Self::Cap(cap) => Ok(Some(*cap)),
+            Self::Unlimited => Ok(Some(U256::MAX)),
+            Self::PreApproved => Ok(None),
+        }
+    }
+}
+
+impl Display for ApprovalStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact => write!(f, "exact"),
+            Self::Cap(cap) => write!(f, "cap {cap}"),
+            Self::Unlimited => write!(f, "unlimited"),
+            Self::PreApproved => write!(f, "pre-approved"),
+        }
+    }
+}
+
+/// Outcome of the approval step of an EVM burn
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    /// The strategy that was applied
+    pub strategy: ApprovalStrategy,
+    /// The allowance available to the burn
+    pub allowance: U256,
+    /// The approval transaction, `None` when the existing allowance was reused
+    pub tx: Option<TxHash>,
+}
+
+impl Display for Approval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.tx {
+            Some(tx) => write!(f, "{} ({}) tx {tx}", self.strategy, self.allowance),
+            None => write!(f, "{} ({}) reused", self.strategy, self.allowance),
+        }
+    }
+}
+
+/// What lets the `TokenMessenger` burn from an owner
+pub(crate) enum AllowanceStep {
+    /// The existing allowance covers the burn
+    Reuse(U256),
+    /// The approve of the target allowance of the [`ApprovalStrategy`]
+    Approve(U256, TransactionRequest),
+}
+
+impl<SrcProvider: Provider<Ethereum> + WalletProvider + Clone, DstProvider>
+    Cctp<SrcProvider, DstProvider>
+{
+    /// Ensures the source chain `TokenMessenger` can burn `amount` USDC from
+    /// the signer, approving according to the configured
+    /// [`ApprovalStrategy`]. A dry run returns the simulation of the approval
+    pub async fn approve_burn(&self, amount: U256) -> Result<Sent<Approval>> {
+        let source_provider = self.source_provider();
+        let strategy = self.approval_strategy;
+        let owner = source_provider.default_signer_address();
+        let (target, tx) = match self.allowance_step(owner, amount).await? {
+            AllowanceStep::Reuse(allowance) => {
+                return Ok(Sent::Broadcast(Approval {
+                    strategy,
+                    allowance,
+                    tx: None,
+                }));
+            }
+            AllowanceStep::Approve(target, tx) => (target, tx),
+        };
+
+        debug!("Approving allowance {target} ({strategy})");
+        let sent = send_with_gas_policy(
+            source_provider,
+            &self.evm_send(self.source_chain(), Operation::Approve),
+            tx,
+        )
+        .await?;
+        Ok(sent.map(|sent| {
+            info!("Approved USDC spending: {}", sent.tx);
+            Approval {
+                strategy,
+                allowance: target,
+                tx: Some(sent.tx),
+            }
+        }))
+    }
+}
+
+impl<SrcProvider: Provider<Ethereum>, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Whether `owner` can burn `amount` with its allowance or must first
+    /// approve according to the [`ApprovalStrategy`]
+    pub(crate) async fn allowance_step(
+        &self,
+        owner: EvmAddress,
+        amount: U256,
+    ) -> Result<AllowanceStep> {
+        let token_messenger: EvmAddress = self.token_messenger_contract()?.try_into()?;
+        let usdc_address: EvmAddress = self.source_chain().usdc_token_address()?.try_into()?;
+        let erc20 = ERC20::new(usdc_address, self.source_provider());
+        let allowance = erc20.allowance(owner, token_messenger).call().await?;
+        if allowance >= amount {
+            debug!("reusing allowance {allowance} for {amount}");
+            return Ok(AllowanceStep::Reuse(allowance));
+        }
+        let Some(target) = self.approval_strategy.target_allowance(amount)? else {
+            return Err(Error::InsufficientAllowance(allowance, amount));
+        };
+        Ok(AllowanceStep::Approve(
+            target,
+            erc20
+                .approve(token_messenger, target)
+                .into_transaction_request(),
+        ))
+    }
+
+    /// Builds the `depositForBurn` transaction on the `TokenMessenger`
+    pub fn burn_transaction(
+        &self,
+        mint_recipient: FixedBytes<32>,
+        amount: U256,
+        destination_caller: FixedBytes<32>,
+        max_fee: U256,
+        min_finality_threshold: u32,
+    ) -> Result<TransactionRequest> {
+        let token_messenger: EvmAddress = self.token_messenger_contract()?.try_into()?;
+        let usdc_address: EvmAddress = self.source_chain().usdc_token_address()?.try_into()?;
+        Ok(
+            TokenMessengerContract::new(token_messenger, self.source_provider())
+                .instance
+                .depositForBurn(
+                    amount,
+                    self.destination_domain_id()?.id(),
+                    mint_recipient,
+                    usdc_address,
+                    destination_caller,
+                    max_fee,
+                    min_finality_threshold,
+                )
+                .into_transaction_request(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    #[rstest]
+    #[case(ApprovalStrategy::Exact, Some(U256::from(10)))]
+    #[case(ApprovalStrategy::Cap(U256::from(10)), Some(U256::from(10)))]
+    #[case(ApprovalStrategy::Cap(U256::from(100)), Some(U256::from(100)))]
+    #[case(ApprovalStrategy::Unlimited, Some(U256::MAX))]
+    #[case(ApprovalStrategy::PreApproved, None)]
+    fn test_target_allowance(#[case] strategy: ApprovalStrategy, #[case] expected: Option<U256>) {
+        assert_eq!(strategy.target_allowance(U256::from(10)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_allowance_override() {
+        let (usdc, owner, spender) = (
+            EvmAddress::repeat_byte(1),
+            EvmAddress::repeat_byte(2),
+            EvmAddress::repeat_byte(3),
+        );
+        let overrides = allowance_override(usdc, owner, spender, U256::from(10));
+        let state_diff = overrides[&usdc].state_diff.as_ref().unwrap();
+        assert_eq!(state_diff.len(), 1);
+        assert_eq!(
+            state_diff.values().next(),
+            Some(&B256::from(U256::from(10)))
+        );
+        let other = allowance_override(usdc, spender, owner, U256::from(10));
+        assert_ne!(other[&usdc].state_diff, overrides[&usdc].state_diff);
+    }
+
+    #[test]
+    fn test_target_allowance_cap_too_low() {
+        let result = ApprovalStrategy::Cap(U256::from(5)).target_allowance(U256::from(10));
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,365 @@
+use {
+    super::{
+        ApprovalStrategy,
+        AttestationConfig,
+        Cctp,
+        ConfirmationPolicy,
+        GasPolicy,
+        IrisEnvironment,
+        Operation,
+        SimulationMode,
+        SolanaTxConfig,
+    },
+    crate::{Address, CctpChain, Error, Result},
+    alloy_chains::Chain,
+    reqwest::Client,
+    std::{collections::HashMap, time::Duration},
+};
+
+/// Builds a [`Cctp`] from explicit options.
+///
+/// Providers are optional, a missing one is `()`, e.g. a [`Cctp`] that only
+/// receives on Solana needs no source provider. Without a destination chain
+/// the source chain is used, which is enough to poll attestations and reclaim
+/// event accounts.
+///
+/// ```ignore
+/// let cctp = Cctp::builder()
+///     .with_source_provider(provider)
+///     .with_source_chain(NamedChain::BaseSepolia)
+///     .with_destination_chain(SOLANA_DEVNET)
+///     .with_recipient(token_account)
+///     .build()?;
+/// ```
+#[derive(Clone)]
+pub struct CctpBuilder<SrcProvider = (), DstProvider = ()> {
+    source_provider: SrcProvider,
+    destination_provider: DstProvider,
+    source_chain: Option<Chain>,
+    destination_chain: Option<Chain>,
+    recipient: Option<Address>,
+    client: Option<Client>,
+    approval_strategy: ApprovalStrategy,
+    gas_policies: HashMap<Chain, GasPolicy>,
+    solana_tx_config: SolanaTxConfig,
+    attestation_config: AttestationConfig,
+    confirmations: HashMap<(Chain, Option<Operation>), (ConfirmationPolicy, Duration)>,
+    iris_environment: Option<IrisEnvironment>,
+    simulation: SimulationMode,
+}
+
+impl Default for CctpBuilder {
+    fn default() -> Self {
+        Self {
+            source_provider: (),
+            destination_provider: (),
+            source_chain: None,
+            destination_chain: None,
+            recipient: None,
+            client: None,
+            approval_strategy: Default::default(),
+            gas_policies: Default::default(),
+            solana_tx_config: Default::default(),
+            attestation_config: Default::default(),
+            confirmations: Default::default(),
+            iris_environment: None,
+            simulation: Default::default(),
+        }
+    }
+}
+
+impl CctpBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Cctp<(), ()> {
+    /// See [`CctpBuilder`]
+    pub fn builder() -> CctpBuilder {
+        CctpBuilder::new()
+    }
+}
+
+impl<SrcProvider, DstProvider> CctpBuilder<SrcProvider, DstProvider> {
+    /// Sets the provider burning on the source chain
+    pub fn with_source_provider<P>(self, source_provider: P) -> CctpBuilder<P, DstProvider> {
+        CctpBuilder {
+            source_provider,
+            destination_provider: self.destination_provider,
+            source_chain: self.source_chain,
+            destination_chain: self.destination_chain,
+            recipient: self.recipient,
+            client: self.client,
+            approval_strategy: self.approval_strategy,
+            gas_policies: self.gas_policies,
+            solana_tx_config: self.solana_tx_config,
+            attestation_config: self.attestation_config,
+            confirmations: self.confirmations,
+            iris_environment: self.iris_environment,
+            simulation: self.simulation,
+        }
+    }
+
+    /// Sets the provider receiving on the destination chain
+    pub fn with_destination_provider<P>(
+        self,
+        destination_provider: P,
+    ) -> CctpBuilder<SrcProvider, P> {
+        CctpBuilder {
+            source_provider: self.source_provider,
+            destination_provider,
+            source_chain: self.source_chain,
+            destination_chain: self.destination_chain,
+            recipient: self.recipient,
+            client: self.client,
+            approval_strategy: self.approval_strategy,
+            gas_policies: self.gas_policies,
+            solana_tx_config: self.solana_tx_config,
+            attestation_config: self.attestation_config,
+            confirmations: self.confirmations,
+            iris_environment: self.iris_environment,
+            simulation: self.simulation,
+        }
+    }
+
+    /// Sets the chain USDC is burned on, required
+    pub fn with_source_chain(mut self, chain: impl Into<Chain>) -> Self {
+        self.source_chain = Some(chain.into());
+        self
+    }
+
+    /// Sets the chain USDC is minted on, defaults to the source chain
+    pub fn with_destination_chain(mut self, chain: impl Into<Chain>) -> Self {
+        self.destination_chain = Some(chain.into());
+        self
+    }
+
+    /// The account credited on the destination chain, it must be of the
+    /// destination domain's [`AddressKind`](crate::AddressKind)
+    pub fn with_recipient(mut self, recipient: impl Into<Address>) -> Self {
+        self.recipient = Some(recipient.into());
+        self
+    }
+
+    /// Sets the HTTP client used for the Iris API, e.g. to share its
+    /// connection pool
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// See [`Cctp::with_approval_strategy`]
+    pub fn with_approval_strategy(mut self, approval_strategy: ApprovalStrategy) -> Self {
+        self.approval_strategy = approval_strategy;
+        self
+    }
+
+    /// See [`Cctp::with_gas_policy`]
+    pub fn with_gas_policy(mut self, chain: impl Into<Chain>, policy: GasPolicy) -> Self {
+        self.gas_policies.insert(chain.into(), policy);
+        self
+    }
+
+    /// See [`Cctp::with_solana_tx_config`]
+    pub fn with_solana_tx_config(mut self, config: SolanaTxConfig) -> Self {
+        self.solana_tx_config = config;
+        self
+    }
+
+    /// See [`Cctp::with_attestation_config`]
+    pub fn with_attestation_config(mut self, attestation_config: AttestationConfig) -> Self {
+        self.attestation_config = attestation_config;
+        self
+    }
+
+    /// See [`Cctp::with_confirmation_policy`]
+    pub fn with_confirmation_policy(
+        mut self,
+        chain: impl Into<Chain>,
+        policy: ConfirmationPolicy,
+        timeout: Duration,
+    ) -> Self {
+        self.confirmations
+            .insert((chain.into(), None), (policy, timeout));
+        self
+    }
+
+    /// See [`Cctp::with_operation_confirmation_policy`]
+    pub fn with_operation_confirmation_policy(
+        mut self,
+        chain: impl Into<Chain>,
+        operation: Operation,
+        policy: ConfirmationPolicy,
+        timeout: Duration,
+    ) -> Self {
+        self.confirmations
+            .insert((chain.into(), Some(operation)), (policy, timeout));
+        self
+    }
+
+    /// Overrides the Iris environment, which otherwise follows the source
+    /// chain's [`CctpChain::sandbox`], e.g. to attest through a proxy of the
+    /// same environment
+    pub fn with_iris_environment(mut self, environment: IrisEnvironment) -> Self {
+        self.iris_environment = Some(environment);
+        self
+    }
+
+    /// See [`Cctp::with_simulation`]
+    pub fn with_simulation(mut self, simulation: SimulationMode) -> Self {
+        self.simulation = simulation;
+        self
+    }
+
+    /// Fails with [`Error::InvalidConfig`] when the options do not describe a
+    /// transfer CCTP can attest
+    pub fn build(self) -> Result<Cctp<SrcProvider, DstProvider>> {
+        let source_chain = self
+            .source_chain
+            .ok_or_else(|| Error::InvalidConfig("source chain is required".to_string()))?;
+        if !source_chain.is_supported() {
+            return Err(Error::InvalidConfig(format!(
+                "{source_chain} cannot be bridged from"
+            )));
+        }
+        let source_domain = source_chain.cctp_domain_id()?;
+        let environment = IrisEnvironment::of_chain(&source_chain);
+        let mut recipient = self.recipient;
+        let destination_chain = match self.destination_chain {
+            Some(destination_chain) => {
+                let destination_domain = destination_chain.cctp_domain_id()?;
+                if destination_domain == source_domain {
+                    return Err(Error::InvalidConfig(format!(
+                        "source and destination are both {source_domain}"
+                    )));
+                }
+                if IrisEnvironment::of_chain(&destination_chain) != environment {
+                    return Err(Error::InvalidConfig(format!(
+                        "{source_chain} and {destination_chain} are not both sandbox or mainnet \
+                         chains"
+                    )));
+                }
+                if let Some(address) = recipient {
+                    let kind = destination_domain.address_kind()?;
+                    recipient = Some(address.with_kind(kind).map_err(|_| {
+                        Error::InvalidConfig(format!(
+                            "{address} is not an account of {destination_domain}"
+                        ))
+                    })?);
+                }
+                destination_chain
+            }
+            None if recipient.is_some() => {
+                return Err(Error::InvalidConfig(
+                    "a recipient requires a destination chain".to_string(),
+                ));
+            }
+            None => source_chain,
+        };
+        Ok(self.assemble(
+            source_chain,
+            destination_chain,
+            recipient.unwrap_or_default(),
+        ))
+    }
+
+    /// The [`Cctp`] of the options and the given route, unchecked, for the
+    /// constructors whose route is fixed by their provider types
+    pub(crate) fn assemble(
+        self,
+        source_chain: Chain,
+        destination_chain: Chain,
+        recipient: Address,
+    ) -> Cctp<SrcProvider, DstProvider> {
+        Cctp {
+            source_provider: self.source_provider,
+            destination_provider: self.destination_provider,
+            source_chain,
+            destination_chain,
+            recipient,
+            client: self.client.unwrap_or_default(),
+            approval_strategy: self.approval_strategy,
+            gas_policies: self.gas_policies,
+            solana_tx_config: self.solana_tx_config,
+            attestation_config: self.attestation_config,
+            confirmations: self.confirmations,
+            iris_environment: self.iris_environment,
+            simulation: self.simulation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{IRIS_API_SANDBOX, NOBLE, SOLANA_DEVNET},
+        alloy_chains::NamedChain,
+        alloy_primitives::address,
+        rstest::rstest,
+    };
+
+    #[test]
+    fn test_build() -> anyhow::Result<()> {
+        let recipient = address!("0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb");
+        let cctp = Cctp::builder()
+            .with_source_chain(SOLANA_DEVNET)
+            .with_destination_chain(NamedChain::BaseSepolia)
+            .with_recipient(recipient)
+            .with_attestation_config(AttestationConfig {
+                max_attempts: 5,
+                poll_interval: 1,
+            })
+            .with_confirmation_policy(
+                NamedChain::BaseSepolia,
+                ConfirmationPolicy::Blocks(3),
+                Duration::from_secs(30),
+            )
+            .build()?;
+        assert_eq!(cctp.recipient(), &Address::from(recipient));
+        assert_eq!(cctp.api_url(), IRIS_API_SANDBOX);
+        assert_eq!(cctp.attestation_config().max_attempts, 5);
+        assert_eq!(
+            cctp.confirmation_policy(&NamedChain::BaseSepolia.into(), Operation::Receive),
+            (ConfirmationPolicy::Blocks(3), Duration::from_secs(30))
+        );
+        let (confirmations, timeout) =
+            crate::get_chain_confirmation_config(&NamedChain::Sepolia.into());
+        assert_eq!(
+            cctp.confirmation_policy(&NamedChain::Sepolia.into(), Operation::Receive),
+            (ConfirmationPolicy::Blocks(confirmations), timeout)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_iris_environment_override() -> anyhow::Result<()> {
+        let cctp = Cctp::builder()
+            .with_source_chain(NamedChain::Base)
+            .with_iris_environment(IrisEnvironment::Sandbox)
+            .build()?;
+        assert_eq!(cctp.api_url(), IRIS_API_SANDBOX);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_without_destination() -> anyhow::Result<()> {
+        let cctp = Cctp::builder().with_source_chain(SOLANA_DEVNET).build()?;
+        assert_eq!(cctp.destination_chain(), &SOLANA_DEVNET);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Cctp::builder())]
+    #[case(Cctp::builder().with_source_chain(NamedChain::Sepolia).with_destination_chain(NamedChain::Base))]
+    #[case(Cctp::builder().with_source_chain(NamedChain::Base).with_destination_chain(SOLANA_DEVNET))]
+    #[case(Cctp::builder().with_source_chain(NamedChain::Base).with_destination_chain(NamedChain::Base))]
+    #[case(Cctp::builder().with_source_chain(NamedChain::Base).with_recipient(solana_pubkey::Pubkey::new_unique()))]
+    #[case(Cctp::builder().with_source_chain(NamedChain::Base).with_destination_chain(NOBLE).with_recipient(solana_pubkey::Pubkey::new_unique()))]
+    #[case(Cctp::builder().with_source_chain(NOBLE).with_destination_chain(NamedChain::Base))]
+    #[case(Cctp::builder().with_source_chain(Chain::from_id(0x00c0_ffee)))]
+    fn test_build_incoherent(#[case] builder: CctpBuilder) {
+        assert!(matches!(builder.build(), Err(Error::InvalidConfig(_))));
+    }
+}
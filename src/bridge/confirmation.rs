@@ -0,0 +1,244 @@
+use {
+    super::Cctp,
+    crate::{Error, Result},
+    alloy_chains::Chain,
+    alloy_network::Ethereum,
+    alloy_primitives::TxHash,
+    alloy_provider::Provider,
+    alloy_rpc_types::BlockNumberOrTag,
+    serde::{Deserialize, Serialize},
+    solana_commitment_config::CommitmentConfig,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signature::Signature,
+    std::{
+        fmt::{Display, Formatter},
+        time::Duration,
+    },
+    tokio::time::{Instant, sleep},
+    tracing::debug,
+};
+
+/// Time between polls of the `safe` or `finalized` block
+const TAG_POLL_INTERVAL: Duration = Duration::from_secs(4);
+
+/// When a sent transaction counts as confirmed.
+///
+/// On Solana only [`ConfirmationPolicy::Finalized`] differs, it waits for the
+/// `finalized` commitment instead of the RPC client's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfirmationPolicy {
+    /// The block of the transaction and `n - 1` blocks on top of it
+    Blocks(u64),
+    /// The block of the transaction is at or below the `safe` block
+    Safe,
+    /// The block of the transaction is at or below the `finalized` block
+    Finalized,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self::Blocks(1)
+    }
+}
+
+impl Display for ConfirmationPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blocks(n) => write!(f, "{n} confirmations"),
+            Self::Safe => write!(f, "safe"),
+            Self::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+impl ConfirmationPolicy {
+    /// Confirmations to watch the transaction for before checking the block
+    /// tag
+    pub fn blocks(&self) -> u64 {
+        match self {
+            Self::Blocks(n) => (*n).max(1),
+            Self::Safe | Self::Finalized => 1,
+        }
+    }
+
+    /// The block tag the transaction's block must reach
+    pub fn block_tag(&self) -> Option<BlockNumberOrTag> {
+        match self {
+            Self::Blocks(_) => None,
+            Self::Safe => Some(BlockNumberOrTag::Safe),
+            Self::Finalized => Some(BlockNumberOrTag::Finalized),
+        }
+    }
+}
+
+/// The transactions a [`ConfirmationPolicy`] can be set for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// The USDC `approve` of EVM burns
+    Approve,
+    /// `depositForBurn`
+    Burn,
+    /// `receiveMessage`, and the creation of the Solana token account it
+    /// credits
+    Receive,
+    /// Reclaiming Solana event accounts
+    Reclaim,
+}
+
+impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The policy and timeout of `operation` transactions sent on `chain`: the
+    /// override for the operation, then the one for the chain, then the
+    /// [`ChainRegistry`](crate::ChainRegistry) confirmations
+    pub fn confirmation_policy(
+        &self,
+        chain: &Chain,
+        operation: Operation,
+    ) -> (ConfirmationPolicy, Duration) {
+        self.confirmations
+            .get(&(*chain, Some(operation)))
+            .or_else(|| self.confirmations.get(&(*chain, None)))
+            .copied()
+            .unwrap_or_else(|| {
+                let (confirmations, timeout) = super::get_chain_confirmation_config(chain);
+                (ConfirmationPolicy::Blocks(confirmations), timeout)
+            })
+    }
+
+    /// Sets the policy of every transaction sent on `chain`
+    pub fn with_confirmation_policy(
+        mut self,
+        chain: impl Into<Chain>,
+        policy: ConfirmationPolicy,
+        timeout: Duration,
+    ) -> Self {
+        self.confirmations
+            .insert((chain.into(), None), (policy, timeout));
+        self
+    }
+
+    /// Sets the policy of `operation` transactions sent on `chain`
+    pub fn with_operation_confirmation_policy(
+        mut self,
+        chain: impl Into<Chain>,
+        operation: Operation,
+        policy: ConfirmationPolicy,
+        timeout: Duration,
+    ) -> Self {
+        self.confirmations
+            .insert((chain.into(), Some(operation)), (policy, timeout));
+        self
+    }
+}
+
+/// Waits until the mined transaction `hash` is in a block at or below the
+/// block tag of `policy`
+pub(crate) async fn wait_for_block_tag<P: Provider<Ethereum>>(
+    provider: &P,
+    policy: ConfirmationPolicy,
+    hash: TxHash,
+    timeout: Duration,
+) -> Result<()> {
+    let Some(tag) = policy.block_tag() else {
+        return Ok(());
+    };
+    let deadline = Instant::now() + timeout;
+    loop {
+        // read the receipt again, a reorg may move the transaction
+        let block = provider
+            .get_transaction_receipt(hash)
+            .await?
+            .and_then(|receipt| receipt.block_number);
+        let tagged = provider
+            .get_block_by_number(tag)
+            .await?
+            .map(|block| block.header.number);
+        debug!("transaction {hash} in block {block:?}, {policy} block {tagged:?}");
+        if block
+            .zip(tagged)
+            .is_some_and(|(block, tagged)| block <= tagged)
+        {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(Error::ConfirmationTimeout {
+                tx: hash.to_string(),
+                policy,
+                timeout,
+            });
+        }
+        sleep(TAG_POLL_INTERVAL).await;
+    }
+}
+
+/// Waits for the `finalized` commitment of `signature` when `policy` is
+/// [`ConfirmationPolicy::Finalized`]
+pub(crate) async fn wait_for_commitment(
+    rpc: &RpcClient,
+    policy: ConfirmationPolicy,
+    signature: &Signature,
+    timeout: Duration,
+) -> Result<()> {
+    if policy != ConfirmationPolicy::Finalized {
+        return Ok(());
+    }
+    tokio::time::timeout(
+        timeout,
+        rpc.poll_for_signature_with_commitment(signature, CommitmentConfig::finalized()),
+    )
+    .await
+    .map_err(|_| Error::ConfirmationTimeout {
+        tx: signature.to_string(),
+        policy,
+        timeout,
+    })??;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Cctp, alloy_chains::NamedChain, rstest::rstest};
+
+    #[rstest]
+    #[case(ConfirmationPolicy::Blocks(0), 1, None)]
+    #[case(ConfirmationPolicy::Blocks(3), 3, None)]
+    #[case(ConfirmationPolicy::Safe, 1, Some(BlockNumberOrTag::Safe))]
+    #[case(ConfirmationPolicy::Finalized, 1, Some(BlockNumberOrTag::Finalized))]
+    fn test_policy(
+        #[case] policy: ConfirmationPolicy,
+        #[case] blocks: u64,
+        #[case] tag: Option<BlockNumberOrTag>,
+    ) {
+        assert_eq!(policy.blocks(), blocks);
+        assert_eq!(policy.block_tag(), tag);
+    }
+
+    #[test]
+    fn test_confirmation_policy_overrides() -> anyhow::Result<()> {
+        let base: Chain = NamedChain::Base.into();
+        let timeout = Duration::from_secs(600);
+        let cctp = Cctp::builder()
+            .with_source_chain(base)
+            .build()?
+            .with_confirmation_policy(base, ConfirmationPolicy::Safe, timeout)
+            .with_operation_confirmation_policy(
+                base,
+                Operation::Burn,
+                ConfirmationPolicy::Finalized,
+                timeout,
+            );
+        assert_eq!(
+            cctp.confirmation_policy(&base, Operation::Burn),
+            (ConfirmationPolicy::Finalized, timeout)
+        );
+        assert_eq!(
+            cctp.confirmation_policy(&base, Operation::Approve),
+            (ConfirmationPolicy::Safe, timeout)
+        );
+        assert_eq!(
+            cctp.confirmation_policy(&NamedChain::Mainnet.into(), Operation::Burn),
+            (ConfirmationPolicy::Blocks(2), Duration::from_secs(300))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,307 @@
+use {
+    super::{
+        Cctp,
+        CostEstimate,
+        Sent,
+        SentTx,
+        Simulation,
+        TransferMode,
+        TxExecution,
+        receipt::display_fromstr,
+    },
+    crate::{Address, Attestation, Domain, Error, Result, Stage, TransferTx},
+    alloy_chains::Chain,
+    alloy_primitives::{FixedBytes, U256},
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt::{Debug, Display, Formatter},
+        str::FromStr,
+    },
+    tracing::{Level, debug, info, instrument},
+};
+
+/// A `depositForBurn` independent of the chain family of the source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BurnRequest {
+    /// USDC amount in the token's base units
+    pub amount: U256,
+    pub destination_domain: Domain,
+    /// Account credited on the destination domain, see
+    /// [`CctpEndpoint::mint_recipient`]
+    pub mint_recipient: FixedBytes<32>,
+    /// The only account allowed to receive the message, zero for anyone
+    pub destination_caller: FixedBytes<32>,
+    pub max_fee: U256,
+    pub min_finality_threshold: u32,
+}
+
+/// One chain family a [`Cctp`] can burn on and receive on.
+///
+/// Implemented for EVM providers and [`SolanaWrapper`](crate::SolanaWrapper),
+/// [`Cctp::bridge`] works for any pair of them.
+#[allow(async_fn_in_trait)]
+pub trait CctpEndpoint: Sized {
+    /// Transaction id, its `Display` form is what Iris expects as
+    /// `transactionHash`
+    type TxId: Clone + Debug + Display + Into<TransferTx>;
+    /// Signs the burn and receive transactions, `()` when the provider has its
+    /// own wallet
+    type Signer: ?Sized;
+
+    /// USDC balance on `chain` of the account that burns for `signer`
+    async fn balance(&self, chain: &Chain, signer: &Self::Signer) -> Result<U256>;
+
+    /// The bytes32 account credited when `recipient` receives on the
+    /// destination chain of `cctp`
+    async fn mint_recipient<S>(
+        _cctp: &Cctp<S, Self>,
+        recipient: &Address,
+    ) -> Result<FixedBytes<32>> {
+        recipient.to_bytes32()
+    }
+
+    /// Burns on the source chain of `cctp`
+    async fn burn<D>(
+        cctp: &Cctp<Self, D>,
+        signer: &Self::Signer,
+        request: &BurnRequest,
+    ) -> Result<Sent<SentTx<Self::TxId>>>;
+
+    /// Receives `attestation` on the destination chain of `cctp`
+    async fn receive<S>(
+        cctp: &Cctp<S, Self>,
+        signer: &Self::Signer,
+        attestation: &Attestation,
+    ) -> Result<Sent<SentTx<Self::TxId>>>;
+
+    /// Whether the message of `attestation` was already received on `chain`
+    async fn is_received(&self, chain: &Chain, attestation: &Attestation) -> Result<bool>;
+
+    /// When the mined `tx` on `chain` was included and what it consumed
+    async fn execution(&self, chain: &Chain, tx: &Self::TxId) -> Result<TxExecution>;
+
+    /// The transactions [`CctpEndpoint::burn`] sends for `request` and their
+    /// costs
+    async fn burn_costs<D>(
+        cctp: &Cctp<Self, D>,
+        request: &BurnRequest,
+    ) -> Result<Vec<CostEstimate>>;
+
+    /// The transactions [`CctpEndpoint::receive`] sends and their costs
+    async fn receive_costs<S>(cctp: &Cctp<S, Self>) -> Result<Vec<CostEstimate>>;
+}
+
+/// Outcome of [`Cctp::bridge`]. The transactions are serialized as their
+/// [`Display`] strings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "B: Display, R: Display",
+    deserialize = "B: FromStr, B::Err: Display, R: FromStr, R::Err: Display"
+))]
+pub struct BridgeResult<B, R> {
+    #[serde(with = "display_fromstr")]
+    pub burn: B,
+    /// `None` when the message was already received, e.g. by a relayer
+    #[serde(with = "display_fromstr::option")]
+    pub recv: Option<R>,
+    pub attestation: Attestation,
+    /// Simulations of the burn and receive, empty when disabled
+    pub simulations: Vec<Simulation>,
+}
+
+impl<B: Display, R: Display> Display for BridgeResult<B, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Burn: {}, Receive: ", self.burn)?;
+        match &self.recv {
+            Some(recv) => write!(f, "{recv}")?,
+            None => write!(f, "already received")?,
+        }
+        write!(f, ", Attestation: {}", self.attestation)
+    }
+}
+
+impl<SrcProvider: CctpEndpoint, DstProvider: CctpEndpoint> Cctp<SrcProvider, DstProvider> {
+    /// Burns `amount` on the source chain, waits for the attestation and
+    /// receives it on the destination chain, unless it was already received.
+    ///
+    /// The finality threshold is the one of `mode` and the fee the one the
+    /// Iris fee API charges for it. Errors carry their [`Stage`], and the burn
+    /// once it was sent. Each transaction is
+    /// simulated first according to the
+    /// [`SimulationMode`](super::SimulationMode), a dry run returns the
+    /// simulations of the source chain transactions in [`Sent::Simulated`].
+    #[instrument(skip(self, source_signer, destination_signer), level = Level::INFO)]
+    pub async fn bridge(
+        &self,
+        amount: U256,
+        mode: TransferMode,
+        source_signer: &SrcProvider::Signer,
+        destination_signer: &DstProvider::Signer,
+    ) -> Result<Sent<BridgeResult<SrcProvider::TxId, DstProvider::TxId>>> {
+        let request = self
+            .fee_burn_request(amount, mode)
+            .await
+            .map_err(|e| e.at_stage(Stage::Burn, None))?;
+        self.bridge_request(&request, source_signer, destination_signer)
+            .await
+    }
+
+    /// Like [`Cctp::bridge`] with the exact burn `request`, e.g. the
+    /// [`Quote::request`](super::Quote::request) of [`Cctp::quote`]
+    #[instrument(skip(self, source_signer, destination_signer), level = Level::INFO)]
+    pub async fn bridge_request(
+        &self,
+        request: &BurnRequest,
+        source_signer: &SrcProvider::Signer,
+        destination_signer: &DstProvider::Signer,
+    ) -> Result<Sent<BridgeResult<SrcProvider::TxId, DstProvider::TxId>>> {
+        let burn_sent = async {
+            let balance = self
+                .source_provider()
+                .balance(self.source_chain(), source_signer)
+                .await?;
+            debug!("balance {balance}");
+            if balance < request.amount {
+                return Err(Error::InsufficientBalance(balance, request.amount));
+            }
+            SrcProvider::burn(self, source_signer, request).await
+        }
+        .await
+        .map_err(|e| e.at_stage(Stage::Burn, None))?;
+        let burn_sent = match burn_sent {
+            Sent::Broadcast(burn_sent) => burn_sent,
+            Sent::Simulated(simulations) => return Ok(Sent::Simulated(simulations)),
+        };
+        self.attest_and_receive(
+            burn_sent.tx,
+            burn_sent.simulation.into_iter().collect(),
+            destination_signer,
+        )
+        .await
+    }
+}
+
+impl<SrcProvider, DstProvider: CctpEndpoint> Cctp<SrcProvider, DstProvider> {
+    /// Waits for the attestation of `burn`, sent outside of [`Cctp::bridge`]
+    /// e.g. by a Safe or a smart account, and receives it on the destination
+    /// chain unless it was already received. A dry run returns the simulation
+    /// of the receive
+    #[instrument(skip(self, destination_signer), level = Level::INFO)]
+    pub async fn resume_bridge<B: Clone + Debug + Display + Into<TransferTx>>(
+        &self,
+        burn: B,
+        destination_signer: &DstProvider::Signer,
+    ) -> Result<Sent<BridgeResult<B, DstProvider::TxId>>> {
+        self.attest_and_receive(burn, Vec::new(), destination_signer)
+            .await
+    }
+
+    async fn attest_and_receive<B: Clone + Display + Into<TransferTx>>(
+        &self,
+        burn: B,
+        mut simulations: Vec<Simulation>,
+        destination_signer: &DstProvider::Signer,
+    ) -> Result<Sent<BridgeResult<B, DstProvider::TxId>>> {
+        let burn_tx: TransferTx = burn.clone().into();
+        let after_burn = |stage| move |e: Error| e.at_stage(stage, Some(burn_tx));
+
+        let config = self.attestation_config();
+        let attestation = self
+            .get_attestation_with_retry(
+                burn.to_string(),
+                Some(config.max_attempts),
+                Some(config.poll_interval),
+            )
+            .await
+            .map_err(after_burn(Stage::Attest))?;
+        let received = self
+            .destination_provider()
+            .is_received(self.destination_chain(), &attestation)
+            .await
+            .map_err(after_burn(Stage::Receive))?;
+        let recv = if received {
+            info!("message of burn {burn} already received");
+            None
+        } else {
+            let recv = DstProvider::receive(self, destination_signer, &attestation)
+                .await
+                .map_err(after_burn(Stage::Receive))?;
+            match recv {
+                Sent::Broadcast(recv) => {
+                    simulations.extend(recv.simulation);
+                    Some(recv.tx)
+                }
+                Sent::Simulated(recv) => {
+                    simulations.extend(recv);
+                    return Ok(Sent::Simulated(simulations));
+                }
+            }
+        };
+        Ok(Sent::Broadcast(BridgeResult {
+            burn,
+            recv,
+            attestation,
+            simulations,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridge_result_serde() {
+        let result = BridgeResult::<TransferTx, TransferTx> {
+            burn: alloy_primitives::TxHash::repeat_byte(1).into(),
+            recv: None,
+            attestation: Attestation {
+                attestation: vec![1],
+                message: vec![2],
+            },
+            simulations: vec![],
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["burn"], result.burn.to_string());
+        assert!(json["recv"].is_null());
+        let decoded: BridgeResult<TransferTx, TransferTx> = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.burn, result.burn);
+        assert_eq!(decoded.recv, None);
+
+        let received = BridgeResult {
+            recv: Some(solana_signature::Signature::from([3; 64]).into()),
+            ..result
+        };
+        let json = serde_json::to_string(&received).unwrap();
+        let decoded: BridgeResult<TransferTx, TransferTx> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.recv, received.recv);
+    }
+
+    #[test]
+    fn test_bridge_result_display() {
+        let attestation = Attestation {
+            attestation: vec![1],
+            message: vec![2],
+        };
+        let received = BridgeResult {
+            burn: "0xburn",
+            recv: Some("0xrecv"),
+            attestation: attestation.clone(),
+            simulations: vec![],
+        };
+        assert_eq!(
+            received.to_string(),
+            "Burn: 0xburn, Receive: 0xrecv, Attestation: attestation: 01, message: 02"
+        );
+        let relayed = BridgeResult::<_, &str> {
+            burn: "0xburn",
+            recv: None,
+            attestation,
+            simulations: vec![],
+        };
+        assert_eq!(
+            relayed.to_string(),
+            "Burn: 0xburn, Receive: already received, Attestation: attestation: 01, message: 02"
+        );
+    }
+}
@@ -1,18 +1,34 @@
 use {
-    super::Cctp,
+    super::{
+        Approval,
+        BurnRequest,
+        Cctp,
+        CctpBuilder,
+        CctpEndpoint,
+        CostEstimate,
+        Operation,
+        Sent,
+        SentTx,
+        TransferMode,
+        TxExecution,
+        approval::allowance_override,
+        gas::send_with_gas_policy,
+        simulation::simulate_evm,
+    },
     crate::{
+        Address,
         Attestation,
         CctpChain,
         ERC20,
+        Error,
         MessageTransmitter,
-        TokenMessengerContract,
+        Stage,
         error::Result,
     },
-    alloy_chains::NamedChain,
+    alloy_chains::Chain,
     alloy_network::Ethereum,
     alloy_primitives::{Address as EvmAddress, TxHash, ruint::aliases::U256},
     alloy_provider::{Provider, WalletProvider},
-    reqwest::Client,
     tracing::{Level, debug, info, instrument},
 };
 // EVM to EVM bridging implementation
@@ -24,94 +40,181 @@ impl<
     pub fn new(
         source_provider: SrcProvider,
         destination_provider: DstProvider,
-        source_chain: NamedChain,
-        destination_chain: NamedChain,
-        recipient: alloy_primitives::Address,
+        source_chain: impl Into<Chain>,
+        destination_chain: impl Into<Chain>,
+        recipient: impl Into<Address>,
     ) -> Self {
-        Self {
-            source_provider,
-            destination_provider,
-            source_chain: source_chain.into(),
-            destination_chain: destination_chain.into(),
-            recipient: recipient.into(),
-            client: Client::new(),
-        }
+        CctpBuilder::new()
+            .with_source_provider(source_provider)
+            .with_destination_provider(destination_provider)
+            .assemble(
+                source_chain.into(),
+                destination_chain.into(),
+                recipient.into(),
+            )
+    }
+
+    /// Waits for the attestation of `burn_hash` and receives it, a dry run
+    /// returns the simulation of the receive
+    #[instrument(level = Level::INFO)]
+    pub async fn recv(
+        &self,
+        burn_hash: TxHash,
+        max_attempts: Option<u32>,
+        poll_interval: Option<u64>,
+    ) -> Result<Sent<(Attestation, SentTx<TxHash>)>> {
+        let burn = Some(burn_hash.into());
+        let attestation = self
+            .get_attestation_evm(burn_hash, max_attempts, poll_interval)
+            .await
+            .map_err(|e| e.at_stage(Stage::Attest, burn))?;
+
+        let recv = self
+            .recv_with_attestation(&attestation)
+            .await
+            .map_err(|e| e.at_stage(Stage::Receive, burn))?;
+        Ok(recv.map(|recv| (attestation, recv)))
+    }
+
+    /// Burns `amount` USDC, waits for the attestation and receives it on the
+    /// destination chain. A dry run returns the simulations of the approval
+    /// and the burn
+    #[instrument(skip(destination_caller), level = Level::INFO)]
+    pub async fn bridge_evm(
+        &self,
+        amount: U256,
+        destination_caller: Option<EvmAddress>,
+        mode: TransferMode,
+    ) -> Result<Sent<super::EvmBridgeResult>> {
+        let (burn_sent, approval) = match self.burn(amount, destination_caller, mode).await? {
+            Sent::Broadcast(sent) => sent,
+            Sent::Simulated(simulations) => return Ok(Sent::Simulated(simulations)),
+        };
+        let mut simulations: Vec<_> = burn_sent.simulation.into_iter().collect();
+        let recv = self.recv(burn_sent.tx, None, None).await?;
+        let (attestation, recv_sent) = match recv {
+            Sent::Broadcast(recv) => recv,
+            Sent::Simulated(recv) => {
+                simulations.extend(recv);
+                return Ok(Sent::Simulated(simulations));
+            }
+        };
+        simulations.extend(recv_sent.simulation);
+        Ok(Sent::Broadcast(super::EvmBridgeResult {
+            approval,
+            burn: burn_sent.tx,
+            recv: recv_sent.tx,
+            attestation,
+            simulations,
+        }))
     }
+}
 
-    #[instrument(skip(max_fee,destination_caller,min_finality_threshold), level = Level::INFO)]
+// EVM burns, the destination may be any CCTP domain
+impl<SrcProvider: Provider<Ethereum> + WalletProvider + Clone, DstProvider>
+    Cctp<SrcProvider, DstProvider>
+{
+    /// Burns `amount` USDC for the recipient on the destination domain,
+    /// which may be any CCTP domain. For Solana the recipient is the USDC
+    /// token account, for Noble, Sui and Aptos receiving is left to the
+    /// destination chain's own tooling. The `maxFee` is the Iris fee of
+    /// `mode`, a dry run returns the simulations of the approval and the burn
+    #[instrument(skip(destination_caller), level = Level::INFO)]
     pub async fn burn(
         &self,
         amount: alloy_primitives::U256,
         destination_caller: Option<EvmAddress>,
-        max_fee: Option<U256>,
-        min_finality_threshold: Option<u32>,
-    ) -> Result<(TxHash, Option<TxHash>)> {
+        mode: TransferMode,
+    ) -> Result<Sent<(SentTx<TxHash>, Approval)>> {
         info!("burning {amount}");
-        let source_provider = self.source_provider();
-        let recipient: EvmAddress = self.recipient().try_into()?;
-        let token_messenger: EvmAddress = self.token_messenger_contract()?.try_into()?;
         let destination_domain = self.destination_domain_id()?;
-        let usdc_address = self.source_chain().usdc_token_address()?.try_into()?;
-        let (confirmations, confirm_timeout) =
-            super::get_chain_confirmation_config(&self.source_chain);
-        let erc20 = ERC20::new(usdc_address, source_provider);
-
-        let usdc_balance = erc20
-            .balanceOf(source_provider.default_signer_address())
-            .call()
+        let recipient = self
+            .recipient()
+            .with_kind(destination_domain.address_kind()?)?;
+        let usdc_balance = self
+            .source_provider()
+            .balance(self.source_chain(), &())
             .await?;
         debug!("balance {usdc_balance}");
-
         if usdc_balance < amount {
-            return Err(crate::Error::InsufficientBalance(usdc_balance, amount));
+            return Err(Error::InsufficientBalance(usdc_balance, amount));
         }
-        let current_allowance = erc20
-            .allowance(source_provider.default_signer_address(), token_messenger)
-            .call()
-            .await?;
-        let approval_hash: Option<TxHash> = if current_allowance < amount {
-            debug!("Approving allowance");
-            let approve_hash = erc20
-                .approve(token_messenger, amount)
-                .send()
-                .await?
-                .with_required_confirmations(confirmations)
-                .with_timeout(Some(confirm_timeout))
-                .watch()
-                .await?;
-            info!("Approved USDC spending: {}", approve_hash);
-            Some(approve_hash)
-        } else {
-            None
-        };
-        let token_messenger = TokenMessengerContract::new(token_messenger, source_provider);
-        let burn_tx = token_messenger.deposit_for_burn_transaction(
-            source_provider.default_signer_address(),
-            recipient.into_word(),
-            destination_domain,
-            usdc_address,
+        self.burn_request(&BurnRequest {
             amount,
-            destination_caller.unwrap_or(EvmAddress::ZERO),
-            max_fee.unwrap_or(U256::from(3)),
-            min_finality_threshold.unwrap_or(0),
-        );
-
-        let burn_hash = source_provider
-            .send_transaction(burn_tx)
-            .await?
-            .with_required_confirmations(confirmations)
-            .with_timeout(Some(confirm_timeout))
-            .watch()
-            .await?;
+            destination_domain,
+            mint_recipient: recipient.to_bytes32()?,
+            destination_caller: destination_caller.unwrap_or(EvmAddress::ZERO).into_word(),
+            max_fee: self.mode_fee(amount, mode).await?,
+            min_finality_threshold: mode.finality_threshold(),
+        })
+        .await
+    }
 
-        Ok((burn_hash, approval_hash))
+    /// Approves according to the [`ApprovalStrategy`](super::ApprovalStrategy)
+    /// and sends the burn of `request`. A dry run simulates the burn with the
+    /// allowance of the simulated approval
+    pub(crate) async fn burn_request(
+        &self,
+        request: &BurnRequest,
+    ) -> Result<Sent<(SentTx<TxHash>, Approval)>> {
+        let at_burn = |e: Error| e.at_stage(Stage::Burn, None);
+        let approval = self
+            .approve_burn(request.amount)
+            .await
+            .map_err(|e| e.at_stage(Stage::Approve, None))?;
+        let burn_tx = self
+            .burn_transaction(
+                request.mint_recipient,
+                request.amount,
+                request.destination_caller,
+                request.max_fee,
+                request.min_finality_threshold,
+            )
+            .map_err(at_burn)?;
+        let approval = match approval {
+            Sent::Broadcast(approval) => approval,
+            Sent::Simulated(mut simulations) => {
+                let overrides = allowance_override(
+                    self.source_chain().usdc_token_address()?.try_into()?,
+                    self.source_provider().default_signer_address(),
+                    self.token_messenger_contract()?.try_into()?,
+                    request.amount,
+                );
+                let simulation = simulate_evm(
+                    self.source_provider(),
+                    Operation::Burn,
+                    burn_tx,
+                    Some(overrides),
+                )
+                .await
+                .map_err(at_burn)?;
+                simulations.push(simulation);
+                return Ok(Sent::Simulated(simulations));
+            }
+        };
+        let burn = send_with_gas_policy(
+            self.source_provider(),
+            &self.evm_send(self.source_chain(), Operation::Burn),
+            burn_tx,
+        )
+        .await
+        .map_err(at_burn)?;
+        Ok(burn.map(|burn| (burn, approval)))
     }
+}
 
-    pub async fn recv_with_attestation(&self, attestation: &Attestation) -> Result<TxHash> {
+// EVM receives, the source may be any CCTP domain
+impl<SrcProvider, DstProvider: Provider<Ethereum> + WalletProvider + Clone>
+    Cctp<SrcProvider, DstProvider>
+{
+    /// Sends the `receiveMessage` of `attestation`, a dry run returns its
+    /// simulation
+    pub async fn recv_with_attestation(
+        &self,
+        attestation: &Attestation,
+    ) -> Result<Sent<SentTx<TxHash>>> {
         let destination_provider = self.destination_provider();
         let message_transmitter: EvmAddress = self.message_transmitter_contract()?.try_into()?;
-        let (confirmations, confirm_timeout) =
-            super::get_chain_confirmation_config(self.destination_chain());
         let message_transmitter =
             MessageTransmitter::new(message_transmitter, destination_provider);
 
@@ -121,27 +224,109 @@ impl<
         );
 
         info!("receiving on chain {}", self.destination_chain());
-        Ok(recv_message_tx
-            .send()
-            .await?
-            .with_required_confirmations(confirmations)
-            .with_timeout(Some(confirm_timeout))
-            .watch()
+        send_with_gas_policy(
+            destination_provider,
+            &self.evm_send(self.destination_chain(), Operation::Receive),
+            recv_message_tx.into_transaction_request(),
+        )
+        .await
+    }
+}
+
+impl<SrcProvider: Provider<Ethereum> + WalletProvider + Clone> Cctp<SrcProvider, ()> {
+    /// A burn only bridge for destinations without a provider, such as Noble,
+    /// Sui and Aptos
+    pub fn new_burn(
+        source_provider: SrcProvider,
+        source_chain: impl Into<Chain>,
+        destination_chain: impl Into<Chain>,
+        recipient: impl Into<Address>,
+    ) -> Self {
+        CctpBuilder::new()
+            .with_source_provider(source_provider)
+            .assemble(
+                source_chain.into(),
+                destination_chain.into(),
+                recipient.into(),
+            )
+    }
+}
+
+/// EVM providers sign with their own wallet
+impl<P: Provider<Ethereum> + WalletProvider + Clone> CctpEndpoint for P {
+    type Signer = ();
+    type TxId = TxHash;
+
+    async fn balance(&self, chain: &Chain, _signer: &()) -> Result<U256> {
+        let usdc_address: EvmAddress = chain.usdc_token_address()?.try_into()?;
+        Ok(ERC20::new(usdc_address, self)
+            .balanceOf(self.default_signer_address())
+            .call()
             .await?)
     }
 
-    #[instrument(level = Level::INFO)]
-    pub async fn recv(
-        &self,
-        burn_hash: TxHash,
-        max_attempts: Option<u32>,
-        poll_interval: Option<u64>,
-    ) -> Result<(Attestation, TxHash)> {
-        let attestation = self
-            .get_attestation_evm(burn_hash, max_attempts, poll_interval)
+    async fn burn<D>(
+        cctp: &Cctp<Self, D>,
+        _signer: &(),
+        request: &BurnRequest,
+    ) -> Result<Sent<SentTx<TxHash>>> {
+        let sent = cctp.burn_request(request).await?;
+        Ok(sent.map(|(burn, approval)| {
+            debug!("burn {} approval {approval}", burn.tx);
+            burn
+        }))
+    }
+
+    async fn receive<S>(
+        cctp: &Cctp<S, Self>,
+        _signer: &(),
+        attestation: &Attestation,
+    ) -> Result<Sent<SentTx<TxHash>>> {
+        cctp.recv_with_attestation(attestation).await
+    }
+
+    async fn is_received(&self, chain: &Chain, attestation: &Attestation) -> Result<bool> {
+        let nonce = attestation
+            .nonce()
+            .ok_or_else(|| Error::AttestationFailed {
+                reason: "message too short".to_string(),
+            })?;
+        let message_transmitter: EvmAddress = chain.message_transmitter_address()?.try_into()?;
+        let used = MessageTransmitter::new(message_transmitter, self)
+            .usedNonces(nonce)
+            .call()
             .await?;
+        Ok(!used.is_zero())
+    }
+
+    async fn execution(&self, _chain: &Chain, tx: &TxHash) -> Result<TxExecution> {
+        let receipt =
+            self.get_transaction_receipt(*tx)
+                .await?
+                .ok_or_else(|| Error::TransactionFailed {
+                    reason: format!("receipt of {tx} not found"),
+                })?;
+        let timestamp = match receipt.block_number {
+            Some(number) => self
+                .get_block_by_number(number.into())
+                .await?
+                .map(|block| block.header.timestamp),
+            None => None,
+        };
+        Ok(TxExecution {
+            timestamp,
+            gas_used: Some(receipt.gas_used),
+        })
+    }
+
+    async fn burn_costs<D>(
+        cctp: &Cctp<Self, D>,
+        request: &BurnRequest,
+    ) -> Result<Vec<CostEstimate>> {
+        cctp.evm_burn_costs(request).await
+    }
 
-        let hash = self.recv_with_attestation(&attestation).await?;
-        Ok((attestation, hash))
+    async fn receive_costs<S>(cctp: &Cctp<S, Self>) -> Result<Vec<CostEstimate>> {
+        cctp.evm_receive_costs().await
     }
 }
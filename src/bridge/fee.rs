@@ -22,22 +22,6 @@ pub struct BurnFee {
 
 pub struct Fees(pub Vec<BurnFee>);
 
-impl Fees {
-    pub fn source_fees(&self) -> u64 {
-        // TODO calculate
-        match self.0.len() {
-            1 => 4, // self.0[0].min_fee,
-            _ => 3,
-        }
-    }
-
-    pub fn source_finality_threshold(&self) -> u32 {
-        match self.0.len() {
-            1 => self.0[0].finality_threshold,
-            _ => 100, // TODO ????
-        }
-    }
-}
 impl Display for BurnFee {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -63,8 +47,8 @@ impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
         let url = format!(
             "{}/v2/burn/USDC/fees/{}/{}",
             self.api_url(),
-            self.source_chain.cctp_domain_id()?,
-            self.destination_chain.cctp_domain_id()?
+            self.source_chain.cctp_domain_id()?.id(),
+            self.destination_chain.cctp_domain_id()?.id()
         );
         debug!("getting fees from {url}");
         let response: Vec<BurnFee> = self.client.get(url).send().await?.json().await?;
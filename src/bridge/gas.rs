@@ -0,0 +1,286 @@
+use {
+    super::{
+        Cctp,
+        ConfirmationPolicy,
+        Operation,
+        Sent,
+        SentTx,
+        SimulationMode,
+        confirmation::wait_for_block_tag,
+        simulation::simulate_evm,
+    },
+    crate::{Error, Result},
+    alloy_chains::Chain,
+    alloy_network::Ethereum,
+    alloy_primitives::TxHash,
+    alloy_provider::{
+        PendingTransactionError,
+        Provider,
+        WalletProvider,
+        WatchTxError,
+        utils::Eip1559Estimation,
+    },
+    alloy_rpc_types::TransactionRequest,
+    std::time::Duration,
+    tracing::{debug, info, warn},
+};
+
+/// Minimum fee increase, in percent, nodes accept for a replacement
+/// transaction
+pub const MIN_REPLACEMENT_BUMP_PERCENT: u128 = 10;
+
+/// EIP-1559 fee policy for the transactions sent on one EVM chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasPolicy {
+    /// Upper bound of `maxFeePerGas` in wei, `None` for no cap
+    pub max_fee_cap: Option<u128>,
+    /// `maxPriorityFeePerGas` in wei, `None` to use the provider's estimate
+    pub priority_fee: Option<u128>,
+    /// Percentage both fees are raised by when a transaction is replaced
+    pub replacement_bump_percent: u128,
+    /// How many times a transaction still pending after the confirmation
+    /// timeout is rebroadcast with the same nonce
+    pub max_replacements: u32,
+}
+
+impl Default for GasPolicy {
+    fn default() -> Self {
+        Self {
+            max_fee_cap: None,
+            priority_fee: None,
+            replacement_bump_percent: 25,
+            max_replacements: 3,
+        }
+    }
+}
+
+impl GasPolicy {
+    /// Applies the policy to the provider's fee estimate, returns
+    /// `(max_fee_per_gas, max_priority_fee_per_gas)`
+    pub fn fees(&self, estimate: Eip1559Estimation) -> (u128, u128) {
+        let priority_fee = self
+            .priority_fee
+            .unwrap_or(estimate.max_priority_fee_per_gas);
+        let max_fee = estimate.max_fee_per_gas.max(priority_fee);
+        let max_fee = self.max_fee_cap.map_or(max_fee, |cap| max_fee.min(cap));
+        (max_fee, priority_fee.min(max_fee))
+    }
+
+    /// Fees of the replacement for a transaction sent with `max_fee` and
+    /// `priority_fee`, `None` when the cap leaves no room for a replacement
+    /// nodes accept
+    pub fn bump(&self, max_fee: u128, priority_fee: u128) -> Option<(u128, u128)> {
+        let percent = 100
+            + self
+                .replacement_bump_percent
+                .max(MIN_REPLACEMENT_BUMP_PERCENT);
+        let min_percent = 100 + MIN_REPLACEMENT_BUMP_PERCENT;
+        let bumped_max_fee = max_fee.saturating_mul(percent) / 100;
+        let bumped_max_fee = self
+            .max_fee_cap
+            .map_or(bumped_max_fee, |cap| bumped_max_fee.min(cap));
+        let bumped_priority_fee = (priority_fee.saturating_mul(percent) / 100).min(bumped_max_fee);
+        if bumped_max_fee < max_fee.saturating_mul(min_percent) / 100
+            || bumped_priority_fee < priority_fee.saturating_mul(min_percent) / 100
+        {
+            return None;
+        }
+        Some((bumped_max_fee, bumped_priority_fee))
+    }
+}
+
+impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Returns the [`GasPolicy`] for `chain`, the default policy when none is
+    /// configured
+    pub fn gas_policy(&self, chain: &Chain) -> GasPolicy {
+        self.gas_policies.get(chain).copied().unwrap_or_default()
+    }
+
+    /// Sets the [`GasPolicy`] for transactions sent on `chain`
+    pub fn with_gas_policy(mut self, chain: impl Into<Chain>, policy: GasPolicy) -> Self {
+        self.gas_policies.insert(chain.into(), policy);
+        self
+    }
+
+    /// The [`EvmSend`] of `operation` transactions sent on `chain`
+    pub(crate) fn evm_send(&self, chain: &Chain, operation: Operation) -> EvmSend {
+        let (confirmation, timeout) = self.confirmation_policy(chain, operation);
+        EvmSend {
+            gas: self.gas_policy(chain),
+            confirmation,
+            timeout,
+            simulation: self.simulation,
+            operation,
+        }
+    }
+}
+
+/// How [`send_with_gas_policy`] simulates, prices and confirms one
+/// transaction
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EvmSend {
+    pub(crate) gas: GasPolicy,
+    pub(crate) confirmation: ConfirmationPolicy,
+    pub(crate) timeout: Duration,
+    pub(crate) simulation: SimulationMode,
+    pub(crate) operation: Operation,
+}
+
+/// Returns the first of `hashes` that has been mined
+async fn mined<P: Provider<Ethereum>>(provider: &P, hashes: &[TxHash]) -> Result<Option<TxHash>> {
+    for hash in hashes {
+        if provider.get_transaction_receipt(*hash).await?.is_some() {
+            return Ok(Some(*hash));
+        }
+    }
+    Ok(None)
+}
+
+/// Simulates `tx` unless disabled, sends it with fees from the gas policy of
+/// `send` and waits until its confirmation policy is met. A dry run only
+/// simulates it.
+///
+/// When the transaction is not confirmed within the timeout it is rebroadcast
+/// with the same nonce and fees bumped by
+/// [`GasPolicy::replacement_bump_percent`], up to
+/// [`GasPolicy::max_replacements`] times.
+pub(crate) async fn send_with_gas_policy<P: Provider<Ethereum> + WalletProvider>(
+    provider: &P,
+    send: &EvmSend,
+    tx: TransactionRequest,
+) -> Result<Sent<SentTx<TxHash>>> {
+    let simulation = match send.simulation {
+        SimulationMode::Disabled => None,
+        SimulationMode::Enabled => {
+            Some(simulate_evm(provider, send.operation, tx.clone(), None).await?)
+        }
+        SimulationMode::DryRun => {
+            return Ok(Sent::Simulated(vec![
+                simulate_evm(provider, send.operation, tx, None).await?,
+            ]));
+        }
+    };
+    let hash = send_and_watch(provider, send, tx)
+        .await
+        .map_err(Error::decode_revert)?;
+    wait_for_block_tag(provider, send.confirmation, hash, send.timeout)
+        .await
+        .map_err(|e| e.after_broadcast(send.operation, hash))?;
+    Ok(Sent::Broadcast(SentTx {
+        tx: hash,
+        simulation,
+    }))
+}
+
+/// Marks `error` as happening after the last of `sent` was broadcast, if any
+fn after_sent(error: Error, operation: Operation, sent: &[TxHash]) -> Error {
+    match sent.last() {
+        Some(tx) => error.after_broadcast(operation, *tx),
+        None => error,
+    }
+}
+
+/// Sends `tx` and waits for the confirmations of `send`, replacing it when it
+/// is stuck
+async fn send_and_watch<P: Provider<Ethereum> + WalletProvider>(
+    provider: &P,
+    send: &EvmSend,
+    mut tx: TransactionRequest,
+) -> Result<TxHash> {
+    let (policy, confirmations, timeout) = (&send.gas, send.confirmation.blocks(), send.timeout);
+    let from = tx.from.unwrap_or_else(|| provider.default_signer_address());
+    let nonce = match tx.nonce {
+        Some(nonce) => nonce,
+        None => provider.get_transaction_count(from).pending().await?,
+    };
+    let (mut max_fee, mut priority_fee) = policy.fees(provider.estimate_eip1559_fees().await?);
+    tx.from = Some(from);
+    tx.nonce = Some(nonce);
+
+    let mut sent: Vec<TxHash> = Vec::new();
+    for attempt in 0..=policy.max_replacements {
+        tx.max_fee_per_gas = Some(max_fee);
+        tx.max_priority_fee_per_gas = Some(priority_fee);
+        debug!(
+            "sending transaction nonce {nonce} max fee {max_fee} priority fee {priority_fee} \
+             attempt {attempt}"
+        );
+        let pending = match provider.send_transaction(tx.clone()).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                // a replacement is rejected when an earlier broadcast was mined meanwhile
+                match mined(provider, &sent).await {
+                    Ok(Some(hash)) => return Ok(hash),
+                    Ok(None) => return Err(after_sent(e.into(), send.operation, &sent)),
+                    Err(e) => return Err(after_sent(e, send.operation, &sent)),
+                }
+            }
+        };
+        let hash = *pending.tx_hash();
+        sent.push(hash);
+        match pending
+            .with_required_confirmations(confirmations)
+            .with_timeout(Some(timeout))
+            .watch()
+            .await
+        {
+            Ok(hash) => return Ok(hash),
+            Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) => {
+                let mined = mined(provider, &sent)
+                    .await
+                    .map_err(|e| after_sent(e, send.operation, &sent))?;
+                if let Some(hash) = mined {
+                    info!(
+                        "transaction {hash} mined without reaching {confirmations} confirmations"
+                    );
+                    return Ok(hash);
+                }
+                warn!("transaction {hash} with nonce {nonce} not confirmed after {timeout:?}");
+                match policy.bump(max_fee, priority_fee) {
+                    Some((bumped_max_fee, bumped_priority_fee)) => {
+                        max_fee = bumped_max_fee;
+                        priority_fee = bumped_priority_fee;
+                    }
+                    None => break,
+                }
+            }
+            Err(e) => return Err(after_sent(e.into(), send.operation, &sent)),
+        }
+    }
+    let stuck = Error::TransactionStuck {
+        nonce,
+        tx: sent.last().copied().unwrap_or_default(),
+    };
+    Err(after_sent(stuck, send.operation, &sent))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    fn estimate(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Eip1559Estimation {
+        Eip1559Estimation {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+
+    #[rstest]
+    #[case(GasPolicy::default(), (100, 2))]
+    #[case(GasPolicy { max_fee_cap: Some(50), ..Default::default() }, (50, 2))]
+    #[case(GasPolicy { priority_fee: Some(10), ..Default::default() }, (100, 10))]
+    #[case(GasPolicy { priority_fee: Some(200), ..Default::default() }, (200, 200))]
+    #[case(GasPolicy { max_fee_cap: Some(1), ..Default::default() }, (1, 1))]
+    fn test_fees(#[case] policy: GasPolicy, #[case] expected: (u128, u128)) {
+        assert_eq!(policy.fees(estimate(100, 2)), expected);
+    }
+
+    #[rstest]
+    #[case(GasPolicy::default(), Some((125, 12)))]
+    #[case(GasPolicy { replacement_bump_percent: 1, ..Default::default() }, Some((110, 11)))]
+    #[case(GasPolicy { max_fee_cap: Some(115), ..Default::default() }, Some((115, 12)))]
+    #[case(GasPolicy { max_fee_cap: Some(105), ..Default::default() }, None)]
+    fn test_bump(#[case] policy: GasPolicy, #[case] expected: Option<(u128, u128)>) {
+        assert_eq!(policy.bump(100, 10), expected);
+    }
+}
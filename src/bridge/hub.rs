@@ -0,0 +1,329 @@
+use {
+    super::{
+        ApprovalStrategy,
+        AttestationConfig,
+        BridgeResult,
+        Cctp,
+        GasPolicy,
+        Sent,
+        SimulationMode,
+        SolanaTxConfig,
+        SolanaWrapper,
+        TransferMode,
+    },
+    crate::{Address, CctpChain, Domain, Error, Result, TransferTx},
+    alloy_chains::Chain,
+    alloy_network::Ethereum,
+    alloy_primitives::U256,
+    alloy_provider::{Provider, WalletProvider},
+    reqwest::Client,
+    solana_signer::Signer,
+    std::{collections::HashMap, sync::Arc},
+    tracing::{Level, info, instrument},
+};
+
+/// The provider, and signer when it has no wallet, of one chain of a
+/// [`CctpHub`]
+#[derive(Clone)]
+enum HubEndpoint<P> {
+    Evm(P),
+    Solana {
+        provider: SolanaWrapper,
+        signer: Arc<dyn Signer + Send + Sync>,
+    },
+}
+
+/// Routes transfers between any of its configured chains, picked per
+/// transfer.
+///
+/// Holds one provider per chain, signers for Solana, and the HTTP client and
+/// settings shared by every transfer. Providers are cloned into each transfer,
+/// so their connections are reused. All EVM chains use the same provider type
+/// `P`, e.g. the one `ProviderBuilder` returns.
+#[derive(Clone)]
+pub struct CctpHub<P> {
+    endpoints: HashMap<Chain, HubEndpoint<P>>,
+    client: Client,
+    attestation_config: AttestationConfig,
+    approval_strategy: ApprovalStrategy,
+    gas_policies: HashMap<Chain, GasPolicy>,
+    solana_tx_config: SolanaTxConfig,
+    simulation: SimulationMode,
+}
+
+impl<P> Default for CctpHub<P> {
+    fn default() -> Self {
+        Self {
+            endpoints: HashMap::new(),
+            client: Client::new(),
+            attestation_config: Default::default(),
+            approval_strategy: Default::default(),
+            gas_policies: Default::default(),
+            solana_tx_config: Default::default(),
+            simulation: Default::default(),
+        }
+    }
+}
+
+impl<P> CctpHub<P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an EVM chain, transactions are signed by the provider's wallet
+    pub fn with_evm(mut self, chain: impl Into<Chain>, provider: P) -> Self {
+        self.endpoints
+            .insert(chain.into(), HubEndpoint::Evm(provider));
+        self
+    }
+
+    /// Adds a Solana cluster, transactions are signed and paid by `signer`
+    pub fn with_solana(
+        mut self,
+        chain: impl Into<Chain>,
+        provider: SolanaWrapper,
+        signer: Arc<dyn Signer + Send + Sync>,
+    ) -> Self {
+        self.endpoints
+            .insert(chain.into(), HubEndpoint::Solana { provider, signer });
+        self
+    }
+
+    /// Sets the HTTP client used for the Iris API
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sets the [`AttestationConfig`] of every transfer
+    pub fn with_attestation_config(mut self, attestation_config: AttestationConfig) -> Self {
+        self.attestation_config = attestation_config;
+        self
+    }
+
+    /// Sets the [`ApprovalStrategy`] of EVM burns
+    pub fn with_approval_strategy(mut self, approval_strategy: ApprovalStrategy) -> Self {
+        self.approval_strategy = approval_strategy;
+        self
+    }
+
+    /// Sets the [`GasPolicy`] for transactions sent on `chain`
+    pub fn with_gas_policy(mut self, chain: impl Into<Chain>, policy: GasPolicy) -> Self {
+        self.gas_policies.insert(chain.into(), policy);
+        self
+    }
+
+    /// Sets the [`SolanaTxConfig`] of Solana transactions
+    pub fn with_solana_tx_config(mut self, config: SolanaTxConfig) -> Self {
+        self.solana_tx_config = config;
+        self
+    }
+
+    /// Sets the [`SimulationMode`] of every transfer
+    pub fn with_simulation(mut self, simulation: SimulationMode) -> Self {
+        self.simulation = simulation;
+        self
+    }
+
+    /// The configured chains
+    pub fn chains(&self) -> impl Iterator<Item = &Chain> {
+        self.endpoints.keys()
+    }
+
+    fn endpoint(&self, chain: &Chain) -> Result<&HubEndpoint<P>> {
+        self.endpoints
+            .get(chain)
+            .ok_or_else(|| Error::ChainNotSupported {
+                chain: chain.to_string(),
+            })
+    }
+
+    fn cctp<S, D>(
+        &self,
+        source_provider: S,
+        destination_provider: D,
+        source_chain: Chain,
+        destination_chain: Chain,
+        recipient: Address,
+    ) -> Result<Cctp<S, D>> {
+        let builder = Cctp::builder()
+            .with_source_provider(source_provider)
+            .with_destination_provider(destination_provider)
+            .with_source_chain(source_chain)
+            .with_destination_chain(destination_chain)
+            .with_recipient(recipient)
+            .with_client(self.client.clone())
+            .with_approval_strategy(self.approval_strategy)
+            .with_solana_tx_config(self.solana_tx_config)
+            .with_attestation_config(self.attestation_config)
+            .with_simulation(self.simulation);
+        self.gas_policies
+            .iter()
+            .fold(builder, |builder, (chain, policy)| {
+                builder.with_gas_policy(*chain, *policy)
+            })
+            .build()
+    }
+}
+
+/// The destination domain, when CCTP can attest a transfer from `source` to
+/// `destination`
+fn check_route(source: &Chain, destination: &Chain) -> Result<Domain> {
+    let source_domain = source.cctp_domain_id()?;
+    let destination_domain = destination.cctp_domain_id()?;
+    if !source.is_supported() {
+        return Err(Error::ChainNotSupported {
+            chain: source.to_string(),
+        });
+    }
+    let reason = if source_domain == destination_domain {
+        format!("both chains are {source_domain}")
+    } else if source.sandbox() != destination.sandbox() {
+        "sandbox and mainnet chains cannot be mixed".to_string()
+    } else {
+        return Ok(destination_domain);
+    };
+    Err(Error::InvalidRoute {
+        from: source.to_string(),
+        to: destination.to_string(),
+        reason,
+    })
+}
+
+/// Erases the transaction types of a [`BridgeResult`]
+fn erase<B: Into<TransferTx>, R: Into<TransferTx>>(
+    result: BridgeResult<B, R>,
+) -> BridgeResult<TransferTx, TransferTx> {
+    BridgeResult {
+        burn: result.burn.into(),
+        recv: result.recv.map(Into::into),
+        attestation: result.attestation,
+        simulations: result.simulations,
+    }
+}
+
+impl<P: Provider<Ethereum> + WalletProvider + Clone> CctpHub<P> {
+    /// Bridges `amount` USDC in `mode` from `source` to `recipient` on
+    /// `destination`, see [`Cctp::bridge`]
+    #[instrument(skip(self, recipient), level = Level::INFO)]
+    pub async fn transfer(
+        &self,
+        source: impl Into<Chain> + std::fmt::Debug,
+        destination: impl Into<Chain> + std::fmt::Debug,
+        recipient: impl Into<Address>,
+        amount: U256,
+        mode: TransferMode,
+    ) -> Result<Sent<BridgeResult<TransferTx, TransferTx>>> {
+        let (source, destination) = (source.into(), destination.into());
+        let destination_domain = check_route(&source, &destination)?;
+        let recipient = recipient
+            .into()
+            .with_kind(destination_domain.address_kind()?)?;
+        info!("transfer {amount} from {source} to {recipient} on {destination}");
+        match (self.endpoint(&source)?, self.endpoint(&destination)?) {
+            (HubEndpoint::Evm(src), HubEndpoint::Evm(dst)) => self
+                .cctp(src.clone(), dst.clone(), source, destination, recipient)?
+                .bridge(amount, mode, &(), &())
+                .await
+                .map(|sent| sent.map(erase)),
+            (HubEndpoint::Evm(src), HubEndpoint::Solana { provider, signer }) => self
+                .cctp(
+                    src.clone(),
+                    provider.clone(),
+                    source,
+                    destination,
+                    recipient,
+                )?
+                .bridge(amount, mode, &(), signer.as_ref())
+                .await
+                .map(|sent| sent.map(erase)),
+            (HubEndpoint::Solana { provider, signer }, HubEndpoint::Evm(dst)) => self
+                .cctp(
+                    provider.clone(),
+                    dst.clone(),
+                    source,
+                    destination,
+                    recipient,
+                )?
+                .bridge(amount, mode, signer.as_ref(), &())
+                .await
+                .map(|sent| sent.map(erase)),
+            (
+                HubEndpoint::Solana {
+                    provider: src,
+                    signer: src_signer,
+                },
+                HubEndpoint::Solana {
+                    provider: dst,
+                    signer: dst_signer,
+                },
+            ) => self
+                .cctp(src.clone(), dst.clone(), source, destination, recipient)?
+                .bridge(amount, mode, src_signer.as_ref(), dst_signer.as_ref())
+                .await
+                .map(|sent| sent.map(erase)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{SOLANA_DEVNET, SOLANA_MAINNET},
+        alloy_chains::NamedChain,
+        rstest::rstest,
+    };
+
+    #[rstest]
+    #[case(NamedChain::Base.into(), NamedChain::Arbitrum.into(), Domain::ARBITRUM)]
+    #[case(NamedChain::BaseSepolia.into(), SOLANA_DEVNET, Domain::SOLANA)]
+    #[case(SOLANA_MAINNET, NamedChain::Mainnet.into(), Domain::ETHEREUM)]
+    fn test_check_route(
+        #[case] source: Chain,
+        #[case] destination: Chain,
+        #[case] domain: Domain,
+    ) -> anyhow::Result<()> {
+        assert_eq!(check_route(&source, &destination)?, domain);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(NamedChain::Base, NamedChain::Base)]
+    #[case(NamedChain::Sepolia, NamedChain::Sepolia)]
+    #[case(NamedChain::Base, NamedChain::ArbitrumSepolia)]
+    #[case(NamedChain::BaseSepolia, NamedChain::Mainnet)]
+    fn test_check_route_invalid(#[case] source: NamedChain, #[case] destination: NamedChain) {
+        assert!(matches!(
+            check_route(&source.into(), &destination.into()),
+            Err(Error::InvalidRoute { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_route_unsupported() {
+        for (source, destination) in [
+            (NamedChain::Base, NamedChain::BinanceSmartChain),
+            (NamedChain::BinanceSmartChain, NamedChain::Base),
+        ] {
+            assert!(matches!(
+                check_route(&source.into(), &destination.into()),
+                Err(Error::ChainNotSupported { .. })
+            ));
+        }
+        // Noble can only be burned to
+        assert!(matches!(
+            check_route(&crate::NOBLE, &NamedChain::Base.into()),
+            Err(Error::ChainNotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unconfigured_chain() {
+        let hub = CctpHub::<()>::new();
+        assert!(matches!(
+            hub.endpoint(&NamedChain::Base.into()),
+            Err(Error::ChainNotSupported { .. })
+        ));
+    }
+}
@@ -0,0 +1,382 @@
+use {
+    super::{
+        BurnRequest,
+        Cctp,
+        GasPolicy,
+        Operation,
+        SolanaProvider,
+        approval::AllowanceStep,
+        confirmation::wait_for_block_tag,
+        gas::EvmSend,
+        recv::recv_message_instruction,
+        solana_tx::{build_transaction, submit_transaction},
+    },
+    crate::{Attestation, CctpChain, Error, MessageTransmitter, Result, Stage},
+    alloy_network::Ethereum,
+    alloy_primitives::{Address as EvmAddress, TxHash, U256},
+    alloy_provider::Provider,
+    alloy_rpc_types::TransactionRequest,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_transaction::Transaction,
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account_idempotent,
+    },
+    tracing::{Level, info, instrument},
+};
+
+/// An unsigned Solana transaction for an external signer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsignedSolanaTx {
+    /// The serialized message every signer signs
+    pub message: Vec<u8>,
+    /// The bincode serialized transaction with empty signatures
+    pub transaction: Vec<u8>,
+    /// The accounts that must sign, in signature order
+    pub signers: Vec<Pubkey>,
+}
+
+impl UnsignedSolanaTx {
+    fn new(tx: &Transaction) -> Result<Self> {
+        let signers = usize::from(tx.message.header.num_required_signatures);
+        Ok(Self {
+            message: tx.message_data(),
+            transaction: bincode::serialize(tx)?,
+            signers: tx.message.account_keys[..signers].to_vec(),
+        })
+    }
+}
+
+/// Fills `tx` from `owner` with its next nonce, the chain id, the estimated
+/// gas, which simulates it, and fees from `policy`
+async fn fill_evm<P: Provider<Ethereum>>(
+    provider: &P,
+    policy: &GasPolicy,
+    owner: EvmAddress,
+    mut tx: TransactionRequest,
+) -> Result<TransactionRequest> {
+    tx.from = Some(owner);
+    tx.nonce = Some(provider.get_transaction_count(owner).pending().await?);
+    tx.chain_id = Some(provider.get_chain_id().await?);
+    tx.gas = Some(
+        provider
+            .estimate_gas(tx.clone())
+            .await
+            .map_err(|e| Error::from(e).decode_revert())?,
+    );
+    let (max_fee, priority_fee) = policy.fees(provider.estimate_eip1559_fees().await?);
+    tx.max_fee_per_gas = Some(max_fee);
+    tx.max_priority_fee_per_gas = Some(priority_fee);
+    Ok(tx)
+}
+
+/// Broadcasts the signed `signed` transaction and waits for the confirmation
+/// policy of `send`
+async fn submit_evm<P: Provider<Ethereum>>(
+    provider: &P,
+    send: &EvmSend,
+    signed: &[u8],
+) -> Result<TxHash> {
+    let pending = provider
+        .send_raw_transaction(signed)
+        .await
+        .map_err(|e| Error::from(e).decode_revert())?;
+    let hash = *pending.tx_hash();
+    pending
+        .with_required_confirmations(send.confirmation.blocks())
+        .with_timeout(Some(send.timeout))
+        .watch()
+        .await
+        .map_err(|e| Error::from(e).after_broadcast(send.operation, hash))?;
+    wait_for_block_tag(provider, send.confirmation, hash, send.timeout)
+        .await
+        .map_err(|e| e.after_broadcast(send.operation, hash))?;
+    Ok(hash)
+}
+
+// EVM burns signed outside the crate. The approve must be mined before the
+// burn is prepared, its gas estimate needs the allowance
+impl<SrcProvider: Provider<Ethereum>, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The approve `owner` must sign before burning `amount`, `None` when its
+    /// allowance covers it or the
+    /// [`ApprovalStrategy`](super::ApprovalStrategy) never approves
+    pub async fn prepare_approve(
+        &self,
+        owner: EvmAddress,
+        amount: U256,
+    ) -> Result<Option<TransactionRequest>> {
+        let Some(tx) = self.approve_transaction(owner, amount).await? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            fill_evm(
+                self.source_provider(),
+                &self.gas_policy(self.source_chain()),
+                owner,
+                tx,
+            )
+            .await?,
+        ))
+    }
+
+    /// The unfilled approve of [`Cctp::prepare_approve`]
+    pub(crate) async fn approve_transaction(
+        &self,
+        owner: EvmAddress,
+        amount: U256,
+    ) -> Result<Option<TransactionRequest>> {
+        Ok(match self.allowance_step(owner, amount).await? {
+            AllowanceStep::Reuse(_) => None,
+            AllowanceStep::Approve(_, tx) => Some(tx),
+        })
+    }
+
+    /// Broadcasts the signed approve of [`Cctp::prepare_approve`]
+    pub async fn submit_approve(&self, signed: &[u8]) -> Result<TxHash> {
+        let hash = submit_evm(
+            self.source_provider(),
+            &self.evm_send(self.source_chain(), Operation::Approve),
+            signed,
+        )
+        .await
+        .map_err(|e| e.at_stage(Stage::Approve, None))?;
+        info!("Approved USDC spending: {hash}");
+        Ok(hash)
+    }
+
+    /// The `depositForBurn` of `request` from `owner`
+    pub async fn prepare_burn(
+        &self,
+        owner: EvmAddress,
+        request: &BurnRequest,
+    ) -> Result<TransactionRequest> {
+        fill_evm(
+            self.source_provider(),
+            &self.gas_policy(self.source_chain()),
+            owner,
+            self.allowance_burn_transaction(request)?,
+        )
+        .await
+    }
+
+    /// The `depositForBurn` of `request`
+    pub(crate) fn allowance_burn_transaction(
+        &self,
+        request: &BurnRequest,
+    ) -> Result<TransactionRequest> {
+        self.burn_transaction(
+            request.mint_recipient,
+            request.amount,
+            request.destination_caller,
+            request.max_fee,
+            request.min_finality_threshold,
+        )
+    }
+
+    /// Broadcasts the signed burn of [`Cctp::prepare_burn`] and waits for its
+    /// attestation
+    #[instrument(skip(self, signed), level = Level::INFO)]
+    pub async fn submit_burn(&self, signed: &[u8]) -> Result<(TxHash, Attestation)> {
+        let hash = submit_evm(
+            self.source_provider(),
+            &self.evm_send(self.source_chain(), Operation::Burn),
+            signed,
+        )
+        .await
+        .map_err(|e| e.at_stage(Stage::Burn, None))?;
+        info!("burn {hash}");
+        let attestation = self
+            .get_attestation_evm(hash, None, None)
+            .await
+            .map_err(|e| e.at_stage(Stage::Attest, Some(hash.into())))?;
+        Ok((hash, attestation))
+    }
+}
+
+// EVM receives signed outside the crate
+impl<SrcProvider, DstProvider: Provider<Ethereum>> Cctp<SrcProvider, DstProvider> {
+    /// The `receiveMessage` of `attestation` from `owner`
+    pub async fn prepare_receive(
+        &self,
+        owner: EvmAddress,
+        attestation: &Attestation,
+    ) -> Result<TransactionRequest> {
+        let destination_provider = self.destination_provider();
+        let message_transmitter: EvmAddress = self.message_transmitter_contract()?.try_into()?;
+        let tx = MessageTransmitter::new(message_transmitter, destination_provider)
+            .receiveMessage(
+                attestation.message.clone().into(),
+                attestation.attestation.clone().into(),
+            )
+            .into_transaction_request();
+        fill_evm(
+            destination_provider,
+            &self.gas_policy(self.destination_chain()),
+            owner,
+            tx,
+        )
+        .await
+    }
+
+    /// Broadcasts the signed receive of [`Cctp::prepare_receive`]
+    pub async fn submit_receive(&self, signed: &[u8]) -> Result<TxHash> {
+        submit_evm(
+            self.destination_provider(),
+            &self.evm_send(self.destination_chain(), Operation::Receive),
+            signed,
+        )
+        .await
+        .map_err(|e| e.at_stage(Stage::Receive, None))
+    }
+}
+
+// Solana burns signed outside the crate
+impl<SrcProvider: SolanaProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The burn of `request` from `owner`, paid by `owner`. The message is
+    /// stored in `event_account`, a new account that must sign too
+    pub async fn prepare_solana_burn(
+        &self,
+        owner: Pubkey,
+        event_account: Pubkey,
+        request: &BurnRequest,
+    ) -> Result<UnsignedSolanaTx> {
+        let instruction = self.solana_burn_instruction(owner, event_account, request)?;
+        let tx = build_transaction(
+            self.source_provider().rpc(),
+            &self.solana_send(self.source_chain(), Operation::Burn),
+            vec![instruction],
+            &owner,
+        )
+        .await?;
+        UnsignedSolanaTx::new(&tx)
+    }
+
+    /// Sends the signed burn of [`Cctp::prepare_solana_burn`], bincode
+    /// serialized, and waits for its attestation
+    #[instrument(skip(self, signed), level = Level::INFO)]
+    pub async fn submit_solana_burn(&self, signed: &[u8]) -> Result<(Signature, Attestation)> {
+        let signature = async {
+            let tx: Transaction = bincode::deserialize(signed)?;
+            submit_transaction(
+                self.source_provider().rpc(),
+                &self.solana_send(self.source_chain(), Operation::Burn),
+                &tx,
+            )
+            .await?
+            .broadcast()
+        }
+        .await
+        .map_err(|e| e.at_stage(Stage::Burn, None))?
+        .tx;
+        info!("burn {signature}");
+        let attestation = self
+            .get_attestation_with_retry(signature.to_string(), None, None)
+            .await
+            .map_err(|e| e.at_stage(Stage::Attest, Some(signature.into())))?;
+        Ok((signature, attestation))
+    }
+}
+
+// Solana receives signed outside the crate
+impl<SrcProvider, DstProvider: SolanaProvider> Cctp<SrcProvider, DstProvider> {
+    /// The receive of `attestation` into the recipient's associated token
+    /// account, paid by `payer`, creating the account first when it does not
+    /// exist
+    pub async fn prepare_solana_receive(
+        &self,
+        payer: Pubkey,
+        attestation: &Attestation,
+    ) -> Result<UnsignedSolanaTx> {
+        let rpc = self.destination_provider().rpc();
+        let recipient: Pubkey = self.recipient().try_into()?;
+        let usdc_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
+        let token_account = get_associated_token_address(&recipient, &usdc_address);
+        let mut instructions = Vec::with_capacity(2);
+        if rpc
+            .get_account_with_commitment(&token_account, rpc.commitment())
+            .await?
+            .value
+            .is_none()
+        {
+            instructions.push(create_associated_token_account_idempotent(
+                &payer,
+                &recipient,
+                &usdc_address,
+                &spl_token::id(),
+            ));
+        }
+        instructions.push(
+            recv_message_instruction(
+                payer,
+                rpc,
+                attestation.clone(),
+                &usdc_address,
+                &token_account,
+                self.source_chain().usdc_token_address()?.to_bytes32()?,
+                self.source_domain_id()?,
+            )
+            .await?,
+        );
+        let tx = build_transaction(
+            rpc,
+            &self.solana_send(self.destination_chain(), Operation::Receive),
+            instructions,
+            &payer,
+        )
+        .await?;
+        UnsignedSolanaTx::new(&tx)
+    }
+
+    /// Sends the signed receive of [`Cctp::prepare_solana_receive`], bincode
+    /// serialized
+    pub async fn submit_solana_receive(&self, signed: &[u8]) -> Result<Signature> {
+        async {
+            let tx: Transaction = bincode::deserialize(signed)?;
+            submit_transaction(
+                self.destination_provider().rpc(),
+                &self.solana_send(self.destination_chain(), Operation::Receive),
+                &tx,
+            )
+            .await?
+            .broadcast()
+        }
+        .await
+        .map(|sent| sent.tx)
+        .map_err(|e: Error| e.at_stage(Stage::Receive, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_instruction::AccountMeta,
+        solana_keypair::Keypair,
+        solana_signer::Signer,
+    };
+
+    #[test]
+    fn test_unsigned_solana_tx() -> anyhow::Result<()> {
+        let (payer, event_account) = (Keypair::new(), Keypair::new());
+        let instruction =
+            solana_instruction::Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(event_account.pubkey(), true),
+            ]);
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        let unsigned = UnsignedSolanaTx::new(&tx)?;
+        assert_eq!(unsigned.signers, vec![
+            payer.pubkey(),
+            event_account.pubkey()
+        ]);
+        assert_eq!(unsigned.message, tx.message_data());
+
+        tx.try_sign(&[&payer, &event_account], tx.message.recent_blockhash)?;
+        let signed: Transaction = bincode::deserialize(&bincode::serialize(&tx)?)?;
+        assert_eq!(signed, tx);
+        let roundtrip: Transaction = bincode::deserialize(&unsigned.transaction)?;
+        assert_eq!(roundtrip.message, tx.message);
+        Ok(())
+    }
+}
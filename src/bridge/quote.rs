@@ -0,0 +1,371 @@
+use {
+    super::{BurnFee, BurnRequest, Cctp, CctpEndpoint, Fees, Operation, SolanaProvider},
+    crate::{CctpChain, ERC20, Error, Result},
+    alloy_network::Ethereum,
+    alloy_primitives::{Address as EvmAddress, FixedBytes, U256},
+    alloy_provider::{Provider, WalletProvider},
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    spl_associated_token_account::get_associated_token_address,
+    spl_token::{solana_program::program_pack::Pack, state::Account as TokenAccount},
+    std::{
+        fmt::{Display, Formatter},
+        time::Duration,
+    },
+    tracing::{Level, debug, instrument},
+};
+
+/// Typical gas of a v2 `depositForBurn`, used when it cannot be estimated
+/// before the approval
+pub const DEPOSIT_FOR_BURN_GAS: u64 = 150_000;
+
+/// Typical gas of a v2 `receiveMessage`, which cannot be estimated before
+/// the attestation
+pub const RECEIVE_MESSAGE_GAS: u64 = 200_000;
+
+/// Seconds until Iris attests a Fast Transfer
+pub const FAST_ATTESTATION_SECONDS: u64 = 20;
+
+/// Lamports per signature of a Solana transaction
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Compute units a Solana transaction may use without a compute unit limit
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+/// Bytes of the `MessageSent` account of a burn without hook data: the
+/// anchor discriminator, rent payer, creation time and length prefixed 376
+/// byte message
+const MESSAGE_SENT_ACCOUNT_LEN: usize = 8 + 32 + 8 + 4 + 376;
+
+/// How fast a burn is attested, see
+/// <https://developers.circle.com/cctp/cctp-finality-and-fees>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransferMode {
+    /// Attested after soft finality, for a fee
+    Fast,
+    /// Attested after hard finality
+    Standard,
+}
+
+impl TransferMode {
+    /// The `minFinalityThreshold` of the burn
+    pub const fn finality_threshold(&self) -> u32 {
+        match self {
+            Self::Fast => 1000,
+            Self::Standard => 2000,
+        }
+    }
+}
+
+impl Display for TransferMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fast => write!(f, "fast"),
+            Self::Standard => write!(f, "standard"),
+        }
+    }
+}
+
+impl BurnFee {
+    /// The fee of burning `amount`, rounded up
+    pub fn fee(&self, amount: U256) -> U256 {
+        (amount * U256::from(self.min_fee)).div_ceil(U256::from(10_000))
+    }
+}
+
+impl Fees {
+    /// The fee of `mode`, `None` when the route does not offer it
+    pub fn for_mode(&self, mode: TransferMode) -> Option<&BurnFee> {
+        self.0
+            .iter()
+            .find(|fee| fee.finality_threshold == mode.finality_threshold())
+    }
+}
+
+/// The estimated cost of one transaction of a [`Quote`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub operation: Operation,
+    /// EVM gas, `None` on Solana
+    pub gas: Option<u64>,
+    /// Whether `gas` comes from `eth_estimateGas` rather than a typical
+    /// figure
+    pub estimated: bool,
+    /// The cost in wei, or in lamports of fees and rent on Solana
+    pub native_fee: u128,
+}
+
+impl Display for CostEstimate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.gas {
+            Some(gas) => write!(
+                f,
+                "{:?}: {gas} gas, {} wei",
+                self.operation, self.native_fee
+            ),
+            None => write!(f, "{:?}: {} lamports", self.operation, self.native_fee),
+        }
+    }
+}
+
+/// The cost and duration of a transfer, see [`Cctp::quote`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quote {
+    pub mode: TransferMode,
+    /// The burn [`Cctp::bridge_request`] sends for this quote
+    pub request: BurnRequest,
+    /// The Circle fee, the burn's `maxFee`
+    pub protocol_fee: U256,
+    /// The least amount minted to the recipient
+    pub net_amount: U256,
+    /// Transactions on the source chain
+    pub source_costs: Vec<CostEstimate>,
+    /// Transactions on the destination chain
+    pub destination_costs: Vec<CostEstimate>,
+    /// Expected time until the message can be received
+    pub eta: Duration,
+}
+
+impl Display for Quote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} transfer of {}: fee {}, net {}, eta {}s",
+            self.mode,
+            self.request.amount,
+            self.protocol_fee,
+            self.net_amount,
+            self.eta.as_secs()
+        )
+    }
+}
+
+impl<SrcProvider: CctpEndpoint, DstProvider: CctpEndpoint> Cctp<SrcProvider, DstProvider> {
+    /// Quotes bridging `amount` in `mode`: the fee from the Iris fee API, the
+    /// transaction costs on both chains and the time until the attestation,
+    /// the average attestation time plus one poll interval
+    #[instrument(skip(self), level = Level::INFO)]
+    pub async fn quote(&self, amount: U256, mode: TransferMode) -> Result<Quote> {
+        let request = self.fee_burn_request(amount, mode).await?;
+        let fee = request.max_fee;
+        let attestation = match mode {
+            TransferMode::Fast => FAST_ATTESTATION_SECONDS
+                .min(self.source_chain().confirmation_average_time_seconds()?),
+            TransferMode::Standard => self.source_chain().confirmation_average_time_seconds()?,
+        };
+        Ok(Quote {
+            mode,
+            request,
+            protocol_fee: fee,
+            net_amount: amount - fee,
+            source_costs: SrcProvider::burn_costs(self, &request).await?,
+            destination_costs: DstProvider::receive_costs(self).await?,
+            eta: Duration::from_secs(attestation + self.attestation_config().poll_interval),
+        })
+    }
+
+    /// The burn of `amount` in `mode`, whose `maxFee` is the fee of the Iris
+    /// fee API
+    pub(crate) async fn fee_burn_request(
+        &self,
+        amount: U256,
+        mode: TransferMode,
+    ) -> Result<BurnRequest> {
+        let fee = self.mode_fee(amount, mode).await?;
+        Ok(BurnRequest {
+            amount,
+            destination_domain: self.destination_domain_id()?,
+            mint_recipient: DstProvider::mint_recipient(self, self.recipient()).await?,
+            destination_caller: FixedBytes::ZERO,
+            max_fee: fee,
+            min_finality_threshold: mode.finality_threshold(),
+        })
+    }
+}
+
+impl<SrcProvider: Provider<Ethereum> + WalletProvider + Clone, DstProvider>
+    Cctp<SrcProvider, DstProvider>
+{
+    /// The approve the [`ApprovalStrategy`](super::ApprovalStrategy) sends and
+    /// the burn of `request`. The burn is estimated when the allowance already
+    /// covers it
+    pub(crate) async fn evm_burn_costs(&self, request: &BurnRequest) -> Result<Vec<CostEstimate>> {
+        let source_provider = self.source_provider();
+        let owner = source_provider.default_signer_address();
+        let token_messenger: EvmAddress = self.token_messenger_contract()?.try_into()?;
+        let usdc_address: EvmAddress = self.source_chain().usdc_token_address()?.try_into()?;
+        let strategy = self.approval_strategy;
+        let erc20 = ERC20::new(usdc_address, source_provider);
+        let (max_fee_per_gas, _) = self
+            .gas_policy(self.source_chain())
+            .fees(source_provider.estimate_eip1559_fees().await?);
+        let cost = |operation, gas, estimated| CostEstimate {
+            operation,
+            gas: Some(gas),
+            estimated,
+            native_fee: u128::from(gas) * max_fee_per_gas,
+        };
+
+        let allowance = erc20.allowance(owner, token_messenger).call().await?;
+        let mut costs = Vec::with_capacity(2);
+        if allowance >= request.amount {
+            let mut burn_tx = self.burn_transaction(
+                request.mint_recipient,
+                request.amount,
+                request.destination_caller,
+                request.max_fee,
+                request.min_finality_threshold,
+            )?;
+            burn_tx.from = Some(owner);
+            let gas = source_provider
+                .estimate_gas(burn_tx)
+                .await
+                .map_err(|e| Error::from(e).decode_revert())?;
+            costs.push(cost(Operation::Burn, gas, true));
+            return Ok(costs);
+        }
+        if let Some(target) = strategy.target_allowance(request.amount)? {
+            let mut approve_tx = erc20
+                .approve(token_messenger, target)
+                .into_transaction_request();
+            approve_tx.from = Some(owner);
+            let gas = source_provider.estimate_gas(approve_tx).await?;
+            costs.push(cost(Operation::Approve, gas, true));
+        }
+        costs.push(cost(Operation::Burn, DEPOSIT_FOR_BURN_GAS, false));
+        Ok(costs)
+    }
+}
+
+impl<SrcProvider, DstProvider: Provider<Ethereum> + WalletProvider + Clone>
+    Cctp<SrcProvider, DstProvider>
+{
+    /// A typical `receiveMessage` at the current fees
+    pub(crate) async fn evm_receive_costs(&self) -> Result<Vec<CostEstimate>> {
+        let (max_fee_per_gas, _) = self
+            .gas_policy(self.destination_chain())
+            .fees(self.destination_provider().estimate_eip1559_fees().await?);
+        Ok(vec![CostEstimate {
+            operation: Operation::Receive,
+            gas: Some(RECEIVE_MESSAGE_GAS),
+            estimated: false,
+            native_fee: u128::from(RECEIVE_MESSAGE_GAS) * max_fee_per_gas,
+        }])
+    }
+}
+
+impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The `maxFee` of burning `amount` in `mode`, the fee of the Iris fee
+    /// API
+    pub(crate) async fn mode_fee(&self, amount: U256, mode: TransferMode) -> Result<U256> {
+        let fees = self.get_fees().await?;
+        debug!("fees {fees}");
+        let fee = fees
+            .for_mode(mode)
+            .ok_or_else(|| {
+                Error::InvalidConfig(format!(
+                    "no {mode} transfer from {} to {}",
+                    self.source_chain(),
+                    self.destination_chain()
+                ))
+            })?
+            .fee(amount);
+        if fee >= amount {
+            return Err(Error::InvalidConfig(format!(
+                "fee {fee} exceeds amount {amount}"
+            )));
+        }
+        Ok(fee)
+    }
+
+    /// Signature and priority fees of a Solana transaction with `signatures`
+    async fn solana_fee(&self, rpc: &RpcClient, signatures: u64) -> Result<u128> {
+        let config = self.solana_tx_config();
+        let price = config.compute_unit_price(rpc, &[]).await?;
+        let units = config
+            .compute_unit_limit
+            .map_or(DEFAULT_COMPUTE_UNIT_LIMIT, u64::from);
+        let priority_fee = (u128::from(price) * u128::from(units)).div_ceil(1_000_000);
+        Ok(u128::from(signatures * LAMPORTS_PER_SIGNATURE) + priority_fee)
+    }
+}
+
+impl<SrcProvider: SolanaProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The burn, signed by the owner and the event account, and the rent of
+    /// the event account, which [`Cctp::reclaim`] returns
+    pub(crate) async fn solana_burn_costs(&self) -> Result<Vec<CostEstimate>> {
+        let rpc = self.source_provider().rpc();
+        let rent = rpc
+            .get_minimum_balance_for_rent_exemption(MESSAGE_SENT_ACCOUNT_LEN)
+            .await?;
+        Ok(vec![CostEstimate {
+            operation: Operation::Burn,
+            gas: None,
+            estimated: true,
+            native_fee: self.solana_fee(rpc, 2).await? + u128::from(rent),
+        }])
+    }
+}
+
+impl<SrcProvider, DstProvider: SolanaProvider> Cctp<SrcProvider, DstProvider> {
+    /// The receive, and the rent of the recipient's associated token account
+    /// when it does not exist yet
+    pub(crate) async fn solana_receive_costs(&self) -> Result<Vec<CostEstimate>> {
+        let rpc = self.destination_provider().rpc();
+        let recipient: Pubkey = self.recipient().try_into()?;
+        let usdc_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
+        let token_account = get_associated_token_address(&recipient, &usdc_address);
+        let rent = match rpc
+            .get_account_with_commitment(&token_account, rpc.commitment())
+            .await?
+            .value
+        {
+            Some(_) => 0,
+            None => {
+                rpc.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)
+                    .await?
+            }
+        };
+        Ok(vec![CostEstimate {
+            operation: Operation::Receive,
+            gas: None,
+            estimated: true,
+            native_fee: self.solana_fee(rpc, 1).await? + u128::from(rent),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    #[rstest]
+    #[case(1, 1_000_000, 100)]
+    #[case(1, 1_000_001, 101)]
+    #[case(0, 1_000_000, 0)]
+    #[case(14, 10_000, 14)]
+    fn test_fee(#[case] min_fee: u32, #[case] amount: u64, #[case] expected: u64) {
+        let fee = BurnFee {
+            finality_threshold: 1000,
+            min_fee,
+        };
+        assert_eq!(fee.fee(U256::from(amount)), U256::from(expected));
+    }
+
+    #[test]
+    fn test_for_mode() {
+        let fees = Fees(vec![
+            BurnFee {
+                finality_threshold: 1000,
+                min_fee: 1,
+            },
+            BurnFee {
+                finality_threshold: 2000,
+                min_fee: 0,
+            },
+        ]);
+        assert_eq!(fees.for_mode(TransferMode::Fast).unwrap().min_fee, 1);
+        assert_eq!(fees.for_mode(TransferMode::Standard).unwrap().min_fee, 0);
+        assert!(Fees(vec![]).for_mode(TransferMode::Fast).is_none());
+    }
+}
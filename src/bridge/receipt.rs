@@ -0,0 +1,265 @@
+use {
+    super::{
+        BridgeResult,
+        Cctp,
+        CctpEndpoint,
+        EvmBridgeResult,
+        EvmSolanaBridgeResult,
+        SolanaEvmBridgeResult,
+    },
+    crate::{Address, Attestation, Caip2, Domain, Error, Result, TransferTx},
+    alloy_primitives::{FixedBytes, TxHash, U256},
+    serde::{Deserialize, Serialize},
+    solana_signature::Signature,
+    tracing::warn,
+};
+
+/// When a mined transaction was included and what it consumed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxExecution {
+    /// Unix timestamp of the block, `None` when the node does not know it
+    pub timestamp: Option<u64>,
+    /// EVM gas, or Solana compute units
+    pub gas_used: Option<u64>,
+}
+
+/// One completed transfer, in a form APIs and databases can store
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeReceipt {
+    /// CAIP-2 id of the source chain, e.g. `eip155:8453`
+    #[serde(with = "display_fromstr")]
+    pub source_chain: Caip2,
+    #[serde(with = "display_fromstr")]
+    pub destination_chain: Caip2,
+    pub source_domain: Domain,
+    pub destination_domain: Domain,
+    /// USDC burned, in base units
+    pub amount: U256,
+    /// The fee Iris charged, the recipient is minted `amount - fee`
+    pub fee: U256,
+    /// The recipient of the [`Cctp`], the owner of the token account on Solana
+    pub recipient: Address,
+    pub nonce: FixedBytes<32>,
+    pub burn: TransferTx,
+    /// `None` when the message was already received, e.g. by a relayer
+    pub receive: Option<TransferTx>,
+    pub attestation: Attestation,
+    /// Unix timestamp of the burn
+    pub burned_at: Option<u64>,
+    /// Unix timestamp of the receive
+    pub received_at: Option<u64>,
+    /// Gas, or compute units on Solana, of the burn
+    pub burn_gas_used: Option<u64>,
+    /// Gas, or compute units on Solana, of the receive
+    pub receive_gas_used: Option<u64>,
+}
+
+impl From<SolanaEvmBridgeResult> for BridgeResult<Signature, TxHash> {
+    fn from(result: SolanaEvmBridgeResult) -> Self {
+        Self {
+            burn: result.burn,
+            recv: Some(result.recv),
+            attestation: result.attestation,
+            simulations: result.simulations,
+        }
+    }
+}
+
+impl From<EvmSolanaBridgeResult> for BridgeResult<TxHash, Signature> {
+    fn from(result: EvmSolanaBridgeResult) -> Self {
+        Self {
+            burn: result.burn,
+            recv: Some(result.recv),
+            attestation: result.attestation,
+            simulations: result.simulations,
+        }
+    }
+}
+
+impl From<EvmBridgeResult> for BridgeResult<TxHash, TxHash> {
+    fn from(result: EvmBridgeResult) -> Self {
+        Self {
+            burn: result.burn,
+            recv: Some(result.recv),
+            attestation: result.attestation,
+            simulations: result.simulations,
+        }
+    }
+}
+
+impl<SrcProvider: CctpEndpoint, DstProvider: CctpEndpoint> Cctp<SrcProvider, DstProvider> {
+    /// The [`BridgeReceipt`] of `result`, reading the block time and gas of
+    /// its transactions from both chains. What a chain cannot tell, e.g. once
+    /// it pruned the transaction, is left `None`
+    pub async fn bridge_receipt(
+        &self,
+        result: &BridgeResult<SrcProvider::TxId, DstProvider::TxId>,
+    ) -> Result<BridgeReceipt> {
+        let attestation = &result.attestation;
+        let too_short = || Error::AttestationFailed {
+            reason: "message too short".to_string(),
+        };
+        let burn = self
+            .source_provider()
+            .execution(self.source_chain(), &result.burn)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("no execution of burn {}: {e}", result.burn);
+                TxExecution::default()
+            });
+        let receive = match &result.recv {
+            Some(tx) => self
+                .destination_provider()
+                .execution(self.destination_chain(), tx)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("no execution of receive {tx}: {e}");
+                    TxExecution::default()
+                }),
+            None => TxExecution::default(),
+        };
+        Ok(BridgeReceipt {
+            source_chain: (*self.source_chain()).into(),
+            destination_chain: (*self.destination_chain()).into(),
+            source_domain: self.source_domain_id()?,
+            destination_domain: self.destination_domain_id()?,
+            amount: attestation.amount().ok_or_else(too_short)?,
+            fee: attestation.fee_executed().ok_or_else(too_short)?,
+            recipient: *self.recipient(),
+            nonce: attestation.nonce().ok_or_else(too_short)?,
+            burn: result.burn.clone().into(),
+            receive: result.recv.clone().map(Into::into),
+            attestation: attestation.clone(),
+            burned_at: burn.timestamp,
+            received_at: receive.timestamp,
+            burn_gas_used: burn.gas_used,
+            receive_gas_used: receive.gas_used,
+        })
+    }
+}
+
+/// Serde through the [`Display`](std::fmt::Display) and
+/// [`FromStr`](std::str::FromStr) strings, e.g. for base58 Solana signatures
+pub(crate) mod display_fromstr {
+    use {
+        serde::{Deserialize, Deserializer, Serializer, de::Error},
+        std::{fmt::Display, str::FromStr},
+    };
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+
+    /// The same for an `Option`, `None` is `null`
+    pub mod option {
+        use {
+            serde::{Deserialize, Deserializer, Serializer, de::Error},
+            std::{fmt::Display, str::FromStr},
+        };
+
+        pub fn serialize<T: Display, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => serializer.serialize_some(&value.to_string()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|value| value.parse().map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        alloy_chains::{Chain, NamedChain},
+    };
+
+    #[test]
+    fn test_bridge_result_from() {
+        let attestation = Attestation {
+            attestation: vec![1],
+            message: vec![2],
+        };
+        let result = BridgeResult::from(EvmBridgeResult {
+            approval: crate::Approval {
+                strategy: crate::ApprovalStrategy::Exact,
+                allowance: U256::from(10),
+                tx: None,
+            },
+            burn: TxHash::repeat_byte(1),
+            recv: TxHash::repeat_byte(2),
+            attestation: attestation.clone(),
+            simulations: vec![],
+        });
+        assert_eq!(result.burn, TxHash::repeat_byte(1));
+        assert_eq!(result.recv, Some(TxHash::repeat_byte(2)));
+        assert!(result.simulations.is_empty());
+
+        let result = BridgeResult::from(SolanaEvmBridgeResult {
+            burn: Signature::from([3; 64]),
+            recv: TxHash::repeat_byte(4),
+            attestation,
+            simulations: vec![],
+        });
+        assert_eq!(result.burn, Signature::from([3; 64]));
+        assert_eq!(result.recv, Some(TxHash::repeat_byte(4)));
+    }
+
+    #[test]
+    fn test_bridge_receipt_serde() {
+        let receipt = BridgeReceipt {
+            source_chain: Chain::from(NamedChain::Base).into(),
+            destination_chain: crate::SOLANA_MAINNET.into(),
+            source_domain: Domain::BASE,
+            destination_domain: Domain::SOLANA,
+            amount: U256::from(1_000_000),
+            fee: U256::from(100),
+            recipient: solana_pubkey::Pubkey::new_unique().into(),
+            nonce: FixedBytes::repeat_byte(7),
+            burn: TxHash::repeat_byte(1).into(),
+            receive: Some(Signature::from([2; 64]).into()),
+            attestation: Attestation {
+                attestation: vec![1],
+                message: vec![2],
+            },
+            burned_at: Some(1_700_000_000),
+            received_at: None,
+            burn_gas_used: Some(150_000),
+            receive_gas_used: None,
+        };
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["source_chain"], "eip155:8453");
+        assert_eq!(json["burn"], TxHash::repeat_byte(1).to_string());
+        assert_eq!(
+            serde_json::from_value::<BridgeReceipt>(json).unwrap(),
+            receipt
+        );
+    }
+}
@@ -1,43 +1,43 @@
 use {
-    super::Cctp,
-    crate::{Address, Result, SolanaProvider},
-    alloy_chains::{Chain, NamedChain},
+    super::{Cctp, CctpBuilder, Operation, Sent, solana_tx::send_instructions},
+    crate::{Result, SolanaProvider},
+    alloy_chains::Chain,
     nitrogen_circle_message_transmitter_v2_encoder::{
         helpers::reclaim_event_account_helpers,
         instructions::reclaim_event_account,
         types::ReclaimEventAccountParams,
     },
-    reqwest::Client,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
     solana_signer::Signer,
     tracing::{debug, info, instrument},
 };
 
-impl<SrcProvider: SolanaProvider + Clone, DstProvider: SolanaProvider>
-    Cctp<SrcProvider, DstProvider>
-{
+impl<SrcProvider: SolanaProvider + Clone> Cctp<SrcProvider, ()> {
+    /// A bridge that only reclaims event accounts on `source_chain`, it has
+    /// no destination
     pub fn new_reclaim(
         source_provider: SrcProvider,
-        destination_provider: DstProvider,
-        source_chain: Chain,
-    ) -> Self {
-        Self {
-            source_provider,
-            destination_provider,
-            source_chain,
-            destination_chain: NamedChain::Mainnet.into(), // does not matter
-            recipient: Address::default(),
-            client: Client::new(),
-        }
+        source_chain: impl Into<Chain>,
+    ) -> Result<Self> {
+        CctpBuilder::new()
+            .with_source_provider(source_provider)
+            .with_source_chain(source_chain)
+            .build()
     }
+}
 
+// Reclaims on the Solana source, the destination provider is not used
+impl<SrcProvider: SolanaProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Closes the claimable event accounts of the signer's past burns,
+    /// refunding their rent. A dry run returns the simulation of every close
     #[instrument(skip(self, signer), level = "debug")]
     pub async fn reclaim<T: Signer + ?Sized>(
         &self,
         signer: &T,
-    ) -> Result<Vec<(Signature, Pubkey)>> {
+    ) -> Result<Sent<Vec<(Signature, Pubkey)>>> {
         let mut results = Vec::new();
+        let mut simulations = Vec::new();
         let rpc = self.source_provider.rpc();
         let reclaim_accounts =
             reclaim_event_account_helpers::find_claimable_accounts(&signer.pubkey(), rpc)
@@ -62,16 +62,30 @@ impl<SrcProvider: SolanaProvider + Clone, DstProvider: SolanaProvider>
                     .destination_message(attestation.message)
                     .build(),
             );
-            let reclaim_tx = reclaim_account
+            let reclaim_ix = reclaim_account
                 .accounts(signer.pubkey(), account.address)
-                .tx();
-            let sig = reclaim_tx
-                .send(rpc, Some(&signer.pubkey()), &[&signer])
-                .await?;
-            info!("processed: {sig} {}", account.address);
-            results.push((sig, account.address));
+                .instruction();
+            let sent = send_instructions(
+                rpc,
+                &self.solana_send(self.source_chain(), Operation::Reclaim),
+                vec![reclaim_ix],
+                &signer.pubkey(),
+                &[signer],
+            )
+            .await?;
+            match sent {
+                Sent::Broadcast(sent) => {
+                    info!("processed: {} {}", sent.tx, account.address);
+                    results.push((sent.tx, account.address));
+                }
+                Sent::Simulated(simulation) => simulations.extend(simulation),
+            }
         }
 
-        Ok(results)
+        if simulations.is_empty() {
+            Ok(Sent::Broadcast(results))
+        } else {
+            Ok(Sent::Simulated(simulations))
+        }
     }
 }
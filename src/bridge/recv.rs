@@ -1,15 +1,23 @@
 use {
-    super::Cctp,
-    crate::{Address, Attestation, CctpChain, Result, SolanaProvider},
-    alloy_chains::{Chain, NamedChain},
-    alloy_primitives::Address as EvmAddress,
+    super::{
+        Cctp,
+        CctpBuilder,
+        Operation,
+        Sent,
+        SentTx,
+        solana_tx::{SolanaSend, send_instructions},
+    },
+    crate::{Attestation, CctpChain, Domain, Result, SolanaProvider},
+    alloy_chains::Chain,
+    alloy_primitives::FixedBytes,
     nitrogen_circle_message_transmitter_v2_encoder::helpers::receive_message_helpers,
     nitrogen_circle_token_messenger_minter_v2_encoder::ID as TOKEN_MESSENGER_PROGRAM_ID,
-    reqwest::Client,
+    solana_instruction::Instruction,
     solana_pubkey::Pubkey,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_signature::Signature,
     solana_signer::Signer,
+    spl_associated_token_account::get_associated_token_address,
     std::fmt::{Debug, Display},
     tracing::{debug, instrument},
 };
@@ -17,13 +25,39 @@ use {
 pub(crate) async fn recv_message_internal<T: Signer + ?Sized>(
     signer: &T,
     rpc: &RpcClient,
+    send: &SolanaSend,
     attestation: Attestation,
     sol_usdc_address: &Pubkey,
-    evm_usdc_address: EvmAddress,
-    source_chain_id: String,
-) -> Result<Signature> {
+    recipient_token_account: &Pubkey,
+    source_usdc: FixedBytes<32>,
+    source_domain: Domain,
+) -> Result<Sent<SentTx<Signature>>> {
     debug!("recv on solana for {}", signer.pubkey());
     let owner = signer.pubkey();
+    let instruction = recv_message_instruction(
+        owner,
+        rpc,
+        attestation,
+        sol_usdc_address,
+        recipient_token_account,
+        source_usdc,
+        source_domain,
+    )
+    .await?;
+    send_instructions(rpc, send, vec![instruction], &owner, &[signer]).await
+}
+
+/// The `receiveMessage` instruction of `attestation` paid by `owner`,
+/// crediting `recipient_token_account`
+pub(crate) async fn recv_message_instruction(
+    owner: Pubkey,
+    rpc: &RpcClient,
+    attestation: Attestation,
+    sol_usdc_address: &Pubkey,
+    recipient_token_account: &Pubkey,
+    source_usdc: FixedBytes<32>,
+    source_domain: Domain,
+) -> Result<Instruction> {
     let fee_recipient_token_account = receive_message_helpers::fee_recipient_token_account(
         rpc,
         &TOKEN_MESSENGER_PROGRAM_ID,
@@ -38,57 +72,69 @@ pub(crate) async fn recv_message_internal<T: Signer + ?Sized>(
         attestation.attestation,
         attestation.message,
     );
-    let remaining_accounts = receive_message_helpers::remaining_accounts(
+    let mut remaining_accounts = receive_message_helpers::remaining_accounts(
         &owner,
-        source_chain_id,
-        evm_usdc_address.into_word(),
+        source_domain.id().to_string(),
+        source_usdc,
         &TOKEN_MESSENGER_PROGRAM_ID,
         sol_usdc_address,
         &fee_recipient_token_account,
     );
+    // the helper assumes the signer's associated token account is the mint
+    // recipient
+    let signer_token_account = get_associated_token_address(&owner, sol_usdc_address);
+    if signer_token_account != *recipient_token_account {
+        for account in remaining_accounts.iter_mut() {
+            if account.pubkey == signer_token_account {
+                account.pubkey = *recipient_token_account;
+            }
+        }
+    }
 
-    builder
-        .remaining_accounts(remaining_accounts)
-        .tx()
-        .send(rpc, Some(&owner), &[&signer])
-        .await
-        .map_err(|e| e.into())
+    Ok(builder.remaining_accounts(remaining_accounts).instruction())
 }
 
-impl<SrcProvider: SolanaProvider, DstProvider: SolanaProvider> Cctp<SrcProvider, DstProvider> {
+impl<DstProvider: SolanaProvider> Cctp<(), DstProvider> {
+    /// A bridge that only receives on Solana, it needs no source provider
     pub fn new_recv(
-        dummy: SrcProvider,
         destination_provider: DstProvider,
-        source_chain: NamedChain,
-        destination_chain: Chain,
-    ) -> Self {
-        Self {
-            source_provider: dummy,
-            destination_provider,
-            source_chain: source_chain.into(),
-            destination_chain,
-            recipient: Address::default(), // does not matter
-            client: Client::new(),
-        }
+        source_chain: impl Into<Chain>,
+        destination_chain: impl Into<Chain>,
+    ) -> Result<Self> {
+        CctpBuilder::new()
+            .with_destination_provider(destination_provider)
+            .with_source_chain(source_chain)
+            .with_destination_chain(destination_chain)
+            .build()
     }
+}
 
+// Solana receives, the source provider is not used
+impl<SrcProvider, DstProvider: SolanaProvider> Cctp<SrcProvider, DstProvider> {
+    /// Waits for the attestation of the burn `tx_hash` and receives it into
+    /// the signer's associated token account, a dry run returns the
+    /// simulation of the receive
     #[instrument(skip(self, signer), level = "debug")]
     pub async fn recv_message_sol<T: Signer + ?Sized>(
         &self,
         signer: &T,
         tx_hash: impl AsRef<str> + Debug + Display,
-    ) -> Result<Signature> {
+    ) -> Result<Sent<SentTx<Signature>>> {
         let attestation = self.get_attestation_with_retry(tx_hash, None, None).await?;
         let destination_provider = self.destination_provider();
         let sol_usdc_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
-        let evm_usdc_address: EvmAddress = self.source_chain().usdc_token_address()?.try_into()?;
+        let source_usdc = self.source_chain().usdc_token_address()?.to_bytes32()?;
+        let recipient_token_account =
+            get_associated_token_address(&signer.pubkey(), &sol_usdc_address);
         recv_message_internal(
             signer,
             destination_provider.rpc(),
+            &self.solana_send(self.destination_chain(), Operation::Receive),
             attestation,
             &sol_usdc_address,
-            evm_usdc_address,
-            self.source_chain().cctp_domain_id()?.to_string(),
+            &recipient_token_account,
+            source_usdc,
+            self.source_domain_id()?,
         )
         .await
     }
@@ -0,0 +1,90 @@
+use {
+    super::{BurnRequest, Cctp},
+    crate::{Error, Result, SafeCall, SafeTransaction, TransactionBatch},
+    alloy_network::Ethereum,
+    alloy_primitives::{Address as EvmAddress, U256},
+    alloy_provider::Provider,
+    alloy_rpc_types::TransactionRequest,
+};
+
+/// The [`SafeCall`] of a contract call `tx`
+fn safe_call(tx: TransactionRequest) -> Result<SafeCall> {
+    let to = tx
+        .to
+        .and_then(|kind| kind.to().copied())
+        .ok_or_else(|| Error::InvalidConfig("Safe call without a target".to_string()))?;
+    Ok(SafeCall {
+        to,
+        value: tx.value.unwrap_or(U256::ZERO),
+        data: tx.input.input().cloned().unwrap_or_default(),
+    })
+}
+
+// Treasury burns executed by a Safe. Once the Safe transaction is executed,
+// `Cctp::resume_bridge` with its hash waits for the attestation and receives
+impl<SrcProvider: Provider<Ethereum>, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The calls `safe` executes to burn `request`: an approve when the Safe's
+    /// allowance is short, then `depositForBurn`
+    pub async fn safe_burn_calls(
+        &self,
+        safe: EvmAddress,
+        request: &BurnRequest,
+    ) -> Result<Vec<SafeCall>> {
+        let approve = self.approve_transaction(safe, request.amount).await?;
+        approve
+            .into_iter()
+            .chain([self.allowance_burn_transaction(request)?])
+            .map(safe_call)
+            .collect()
+    }
+
+    /// The burn of `request` by `safe` as one `MultiSendCallOnly` transaction
+    /// to propose and execute
+    pub async fn safe_multi_send(
+        &self,
+        safe: EvmAddress,
+        request: &BurnRequest,
+    ) -> Result<SafeTransaction> {
+        Ok(SafeTransaction::multi_send(
+            &self.safe_burn_calls(safe, request).await?,
+        ))
+    }
+
+    /// The burn of `request` by `safe` as a batch file for the Safe{Wallet}
+    /// Transaction Builder
+    pub async fn safe_transaction_batch(
+        &self,
+        safe: EvmAddress,
+        request: &BurnRequest,
+    ) -> Result<TransactionBatch> {
+        let calls = self.safe_burn_calls(safe, request).await?;
+        Ok(TransactionBatch::new(
+            self.source_chain().id(),
+            safe,
+            "CCTP burn",
+            format!(
+                "Burn {} USDC base units to domain {}",
+                request.amount, request.destination_domain
+            ),
+            &calls,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloy_primitives::Bytes};
+
+    #[test]
+    fn test_safe_call() {
+        let to = EvmAddress::repeat_byte(0x11);
+        let tx = TransactionRequest::default()
+            .to(to)
+            .input(Bytes::from_static(&[0xab]).into());
+        let call = safe_call(tx).unwrap();
+        assert_eq!(call.to, to);
+        assert_eq!(call.value, U256::ZERO);
+        assert_eq!(call.data, Bytes::from_static(&[0xab]));
+        assert!(safe_call(TransactionRequest::default()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use {
+    super::{Cctp, Operation},
+    crate::{CctpRevert, Error, Result},
+    alloy_network::Ethereum,
+    alloy_provider::{Provider, WalletProvider},
+    alloy_rpc_types::{TransactionRequest, state::StateOverride},
+    serde::{Deserialize, Serialize},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_transaction::Transaction,
+    std::fmt::{Display, Formatter},
+    tracing::debug,
+};
+
+/// Whether transactions are simulated before they are broadcast
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SimulationMode {
+    /// Broadcast without simulating
+    Disabled,
+    /// Simulate, and broadcast only when the simulation succeeds
+    #[default]
+    Enabled,
+    /// Simulate every transaction and broadcast none, see
+    /// [`Sent::Simulated`]. A transaction that depends on an earlier one is
+    /// simulated as if that one had landed, e.g. an EVM burn with the allowance
+    /// of its approval
+    DryRun,
+}
+
+/// The cost a simulation estimated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedCost {
+    /// EVM gas, from `eth_estimateGas`
+    Gas(u64),
+    /// Solana compute units consumed by `simulateTransaction`
+    ComputeUnits(u64),
+}
+
+impl Display for SimulatedCost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gas(gas) => write!(f, "{gas} gas"),
+            Self::ComputeUnits(units) => write!(f, "{units} compute units"),
+        }
+    }
+}
+
+/// A successful simulation of an `operation` transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Simulation {
+    pub operation: Operation,
+    pub cost: SimulatedCost,
+}
+
+impl Display for Simulation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} using {}", self.operation, self.cost)
+    }
+}
+
+/// A broadcast transaction and its simulation, `None` when
+/// [`SimulationMode::Disabled`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SentTx<T> {
+    pub tx: T,
+    pub simulation: Option<Simulation>,
+}
+
+/// Outcome of sending a transaction or a whole transfer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sent<T> {
+    Broadcast(T),
+    /// A [`SimulationMode::DryRun`] simulated every step and broadcast nothing
+    Simulated(Vec<Simulation>),
+}
+
+impl<T> Sent<T> {
+    /// The broadcast outcome, or [`Error::DryRun`] for callers that need one
+    pub fn broadcast(self) -> Result<T> {
+        match self {
+            Self::Broadcast(sent) => Ok(sent),
+            Self::Simulated(simulations) => Err(Error::DryRun(simulations)),
+        }
+    }
+
+    /// Maps the broadcast outcome
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Sent<U> {
+        match self {
+            Self::Broadcast(sent) => Sent::Broadcast(f(sent)),
+            Self::Simulated(simulations) => Sent::Simulated(simulations),
+        }
+    }
+}
+
+impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Returns the [`SimulationMode`] of the transactions sent
+    pub fn simulation(&self) -> SimulationMode {
+        self.simulation
+    }
+
+    /// Sets the [`SimulationMode`] of the transactions sent
+    pub fn with_simulation(mut self, simulation: SimulationMode) -> Self {
+        self.simulation = simulation;
+        self
+    }
+}
+
+/// Runs `eth_call` then `eth_estimateGas` on `tx` from the provider's signer,
+/// on top of `overrides` of the state
+pub(crate) async fn simulate_evm<P: Provider<Ethereum> + WalletProvider>(
+    provider: &P,
+    operation: Operation,
+    mut tx: TransactionRequest,
+    overrides: Option<StateOverride>,
+) -> Result<Simulation> {
+    tx.from
+        .get_or_insert_with(|| provider.default_signer_address());
+    let simulate = async {
+        provider
+            .call(tx.clone())
+            .overrides_opt(overrides.clone())
+            .await?;
+        Ok::<_, Error>(provider.estimate_gas(tx).overrides_opt(overrides).await?)
+    };
+    let gas = simulate.await.map_err(Error::decode_revert)?;
+    debug!("simulated {operation:?} using {gas} gas");
+    Ok(Simulation {
+        operation,
+        cost: SimulatedCost::Gas(gas),
+    })
+}
+
+/// Runs `simulateTransaction` on the signed `tx`, a failure is decoded from
+/// the program logs
+pub(crate) async fn simulate_solana(
+    rpc: &RpcClient,
+    operation: Operation,
+    tx: &Transaction,
+) -> Result<Simulation> {
+    let result = rpc.simulate_transaction(tx).await?.value;
+    if let Some(err) = result.err {
+        let logs = result.logs.unwrap_or_default();
+        return Err(match CctpRevert::from_solana_logs(&logs) {
+            Some(revert) => Error::Revert(revert),
+            None => Error::SimulationFailed {
+                reason: format!("{err:?}"),
+                logs,
+            },
+        });
+    }
+    let units = result.units_consumed.unwrap_or_default();
+    debug!("simulated {operation:?} using {units} compute units");
+    Ok(Simulation {
+        operation,
+        cost: SimulatedCost::ComputeUnits(units),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_display() {
+        let burn = Simulation {
+            operation: Operation::Burn,
+            cost: SimulatedCost::Gas(120_000),
+        };
+        assert_eq!(burn.to_string(), "Burn using 120000 gas");
+        let receive = Simulation {
+            operation: Operation::Receive,
+            cost: SimulatedCost::ComputeUnits(95_000),
+        };
+        assert_eq!(receive.to_string(), "Receive using 95000 compute units");
+        assert_eq!(SimulationMode::default(), SimulationMode::Enabled);
+    }
+
+    #[test]
+    fn test_sent() {
+        let simulations = vec![Simulation {
+            operation: Operation::Approve,
+            cost: SimulatedCost::Gas(46_000),
+        }];
+        assert_eq!(Sent::Broadcast(1).map(|tx| tx + 1).broadcast().unwrap(), 2);
+        let simulated = Sent::<u8>::Simulated(simulations.clone()).map(|tx| tx + 1);
+        assert_eq!(simulated, Sent::Simulated(simulations.clone()));
+        let error = simulated.broadcast().unwrap_err();
+        assert_eq!(error.dry_run(), Some(simulations.as_slice()));
+    }
+}
@@ -1,29 +1,51 @@
 use {
-    super::Cctp,
+    super::{
+        BurnRequest,
+        Cctp,
+        CctpBuilder,
+        CctpEndpoint,
+        CostEstimate,
+        Operation,
+        Sent,
+        SentTx,
+        SimulationMode,
+        TransferMode,
+        TxExecution,
+    },
     crate::{
+        Address,
+        Attestation,
         CctpChain,
-        ERC20,
         Error,
-        MessageTransmitter,
         Result,
-        TokenMessengerContract,
-        bridge::recv,
+        Stage,
+        bridge::{
+            recv,
+            solana_tx::send_instructions,
+            token_account::{SolanaRecipientAccount, create_associated_token_account},
+        },
     },
-    alloy_chains::NamedChain,
+    alloy_chains::Chain,
     alloy_network::{Ethereum, NetworkWallet},
-    alloy_primitives::{Address as EvmAddress, ruint::aliases::U256},
+    alloy_primitives::{Address as EvmAddress, FixedBytes, ruint::aliases::U256},
     alloy_provider::{Provider, WalletProvider},
     nitrogen_circle_token_messenger_minter_v2_encoder::{
         helpers::deposit_for_burn_instruction,
         types::DepositForBurnParams,
     },
-    reqwest::Client,
+    solana_instruction::Instruction,
     solana_keypair::Keypair,
     solana_pubkey::Pubkey,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_signature::Signature,
     solana_signer::{Signer, SignerError, signers::Signers},
-    std::{sync::Arc, time::Duration},
+    solana_transaction_status_client_types::UiTransactionEncoding,
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token::{solana_program::program_pack::Pack, state::Account as TokenAccount},
+    std::sync::Arc,
     tracing::{Level, debug, info, instrument},
 };
 
@@ -96,6 +118,107 @@ impl SolanaProvider for SolanaWrapper {
     }
 }
 
+/// Seed of the message transmitter PDAs that mark a nonce as received
+const USED_NONCE_SEED: &[u8] = b"used_nonce";
+
+// Solana burns, the destination may be any CCTP domain
+impl<SrcProvider: SolanaProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Sends the `depositForBurn` of `request` burning from `owner`, the
+    /// message is stored in `event_account`. Both must sign in `signers`
+    pub(crate) async fn solana_burn<S: Signers + ?Sized>(
+        &self,
+        owner: Pubkey,
+        event_account: Pubkey,
+        signers: &S,
+        request: &BurnRequest,
+    ) -> Result<Sent<SentTx<Signature>>> {
+        info!("using message_sent_event_account {event_account}");
+        send_instructions(
+            self.source_provider().rpc(),
+            &self.solana_send(self.source_chain(), Operation::Burn),
+            vec![self.solana_burn_instruction(owner, event_account, request)?],
+            &owner,
+            signers,
+        )
+        .await
+    }
+
+    /// The `depositForBurn` instruction of `request` burning from `owner`,
+    /// the message is stored in `event_account`
+    pub(crate) fn solana_burn_instruction(
+        &self,
+        owner: Pubkey,
+        event_account: Pubkey,
+        request: &BurnRequest,
+    ) -> Result<Instruction> {
+        let amount = u64::try_from(request.amount)
+            .map_err(|_| Error::InvalidConfig(format!("amount {} exceeds u64", request.amount)))?;
+        let max_fee = u64::try_from(request.max_fee).map_err(|_| {
+            Error::InvalidConfig(format!("max fee {} exceeds u64", request.max_fee))
+        })?;
+        if max_fee > amount {
+            return Err(Error::SolanaInvalidFee(max_fee, amount));
+        }
+        let usdc_address: Pubkey = self.source_chain().usdc_token_address()?.try_into()?;
+        let params = DepositForBurnParams::builder()
+            .amount(amount)
+            .destination_domain(request.destination_domain.id())
+            .destination_caller(Pubkey::new_from_array(request.destination_caller.0))
+            .mint_recipient(Pubkey::new_from_array(request.mint_recipient.0))
+            .max_fee(max_fee)
+            .min_finality_threshold(request.min_finality_threshold)
+            .build();
+        Ok(deposit_for_burn_instruction(params, owner, event_account, usdc_address).instruction())
+    }
+}
+
+// Solana receives, the source may be any CCTP domain
+impl<SrcProvider, DstProvider: SolanaProvider> Cctp<SrcProvider, DstProvider> {
+    /// Receives `attestation` into `token_account` of the recipient, creating
+    /// it first when `create` is set with rent paid by `payer`. A dry run
+    /// simulates both in one transaction
+    pub(crate) async fn solana_receive(
+        &self,
+        signer: &dyn Signer,
+        payer: &dyn Signer,
+        attestation: Attestation,
+        token_account: &Pubkey,
+        create: bool,
+    ) -> Result<Sent<SentTx<Signature>>> {
+        let rpc = self.destination_provider().rpc();
+        let send = self.solana_send(self.destination_chain(), Operation::Receive);
+        let recipient: Pubkey = self.recipient().try_into()?;
+        let usdc_sol_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
+        let receive = recv::recv_message_instruction(
+            signer.pubkey(),
+            rpc,
+            attestation,
+            &usdc_sol_address,
+            token_account,
+            self.source_chain().usdc_token_address()?.to_bytes32()?,
+            self.source_domain_id()?,
+        )
+        .await?;
+        if create && self.simulation == SimulationMode::DryRun {
+            let create = create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &recipient,
+                &usdc_sol_address,
+                &spl_token::id(),
+            );
+            return send_instructions(rpc, &send, vec![create, receive], &signer.pubkey(), &[
+                signer, payer,
+            ])
+            .await;
+        }
+        if create {
+            create_associated_token_account(rpc, &send, payer, &recipient, &usdc_sol_address)
+                .await?;
+        }
+        send_instructions(rpc, &send, vec![receive], &signer.pubkey(), &[signer]).await
+    }
+}
+
 // Solana to EVM bridging implementation
 impl<SrcProvider: SolanaProvider, DstProvider: Provider<Ethereum> + WalletProvider + Clone>
     Cctp<SrcProvider, DstProvider>
@@ -103,103 +226,87 @@ impl<SrcProvider: SolanaProvider, DstProvider: Provider<Ethereum> + WalletProvid
     pub fn new_solana_evm(
         source_provider: SrcProvider,
         destination_provider: DstProvider,
-        source_chain: alloy_chains::Chain,
-        destination_chain: NamedChain,
+        source_chain: impl Into<Chain>,
+        destination_chain: impl Into<Chain>,
     ) -> Self {
         let recipient = destination_provider.wallet().default_signer_address();
-        Self {
-            source_provider,
-            destination_provider,
-            source_chain,
-            destination_chain: destination_chain.into(),
-            recipient: recipient.into(),
-            client: Client::new(),
-        }
+        CctpBuilder::new()
+            .with_source_provider(source_provider)
+            .with_destination_provider(destination_provider)
+            .assemble(
+                source_chain.into(),
+                destination_chain.into(),
+                recipient.into(),
+            )
     }
 
-    #[instrument(skip(self,signers,max_fee,destination_caller,min_finality_threshold), level = Level::INFO
-    )]
+    /// Burns `lamports` USDC on Solana and receives on the EVM destination
+    /// chain, the `maxFee` is the Iris fee of `mode`. A dry run returns the
+    /// simulation of the burn
+    #[instrument(skip(self, signers, destination_caller), level = Level::INFO)]
     pub async fn bridge_sol_evm<S: Signer>(
         &self,
         lamports: u64,
         signers: SolanSigners<S>,
         destination_caller: Option<Pubkey>,
-        max_fee: Option<u64>,
-        min_finality_threshold: Option<u32>,
-        //        attestation_poll_interval: Option<u64>,
-    ) -> Result<super::SolanaEvmBridgeResult> {
+        mode: TransferMode,
+    ) -> Result<Sent<super::SolanaEvmBridgeResult>> {
         info!("burning {lamports}");
-        let source_provider = self.source_provider();
-        let destination_provider = self.destination_provider();
         let recipient: EvmAddress = self.recipient().try_into()?;
-        let mint_recipient = Pubkey::new_from_array(recipient.into_word().into());
-        let message_transmitter_evm: EvmAddress = self
-            .destination_chain()
-            .message_transmitter_address()?
-            .try_into()?;
-        let destination_domain = self.destination_domain_id()?;
-        let usdc_address: Pubkey = self.source_chain().usdc_token_address()?.try_into()?;
-        let owner = signers.owner.pubkey();
-        let fees = self.get_fees().await?;
-        debug!("fees {fees}");
-        let params = DepositForBurnParams::builder()
-            .amount(lamports)
-            .destination_domain(destination_domain)
-            .destination_caller(destination_caller.unwrap_or_default())
-            .mint_recipient(mint_recipient)
-            .max_fee(max_fee.unwrap_or(fees.source_fees()))
-            .min_finality_threshold(
-                min_finality_threshold.unwrap_or(fees.source_finality_threshold()),
+        let amount = U256::from(lamports);
+        let request = BurnRequest {
+            amount,
+            destination_domain: self.destination_domain_id()?,
+            mint_recipient: recipient.into_word(),
+            destination_caller: destination_caller.unwrap_or_default().to_bytes().into(),
+            max_fee: self.mode_fee(amount, mode).await?,
+            min_finality_threshold: mode.finality_threshold(),
+        };
+        let burn_sent = self
+            .solana_burn(
+                signers.owner.pubkey(),
+                signers.message_sent_event_account.pubkey(),
+                &signers,
+                &request,
             )
-            .build();
-        if params.max_fee > lamports {
-            return Err(Error::SolanaInvalidFee(params.max_fee, lamports));
-        }
-        let deposit_for_burn = deposit_for_burn_instruction(
-            params,
-            owner,
-            signers.message_sent_event_account.pubkey(),
-            usdc_address,
-        );
-
-        info!(
-            "using message_sent_event_account {}",
-            signers.message_sent_event_account.pubkey()
-        );
-        let burn_hash = deposit_for_burn
-            .tx()
-            .send(source_provider.rpc(), Some(&owner), &signers)
-            .await?;
+            .await
+            .map_err(|e| e.at_stage(Stage::Burn, None))?;
+        let burn_sent = match burn_sent {
+            Sent::Broadcast(burn_sent) => burn_sent,
+            Sent::Simulated(simulations) => return Ok(Sent::Simulated(simulations)),
+        };
+        let mut simulations: Vec<_> = burn_sent.simulation.into_iter().collect();
+        let burn_hash = burn_sent.tx;
+        let burn = Some(burn_hash.into());
 
         let attestation = self
-            .get_attestation_with_retry(burn_hash.to_string(), None, Some(10))
-            .await?;
-
-        let message_transmitter =
-            MessageTransmitter::new(message_transmitter_evm, destination_provider);
-
-        let recv_message_tx = message_transmitter.receiveMessage(
-            attestation.message.clone().into(),
-            attestation.attestation.clone().into(),
-        );
+            .get_attestation_with_retry(burn_hash.to_string(), None, None)
+            .await
+            .map_err(|e| e.at_stage(Stage::Attest, burn))?;
 
         info!(
             "recv {lamports} on chain {} recipient {recipient}",
             self.destination_chain(),
         );
-        let recv_hash = recv_message_tx
-            .send()
-            .await?
-            .with_required_confirmations(2)
-            .with_timeout(Some(Duration::from_secs(90)))
-            .watch()
-            .await?;
+        let recv_sent = match self
+            .recv_with_attestation(&attestation)
+            .await
+            .map_err(|e| e.at_stage(Stage::Receive, burn))?
+        {
+            Sent::Broadcast(recv_sent) => recv_sent,
+            Sent::Simulated(recv) => {
+                simulations.extend(recv);
+                return Ok(Sent::Simulated(simulations));
+            }
+        };
+        simulations.extend(recv_sent.simulation);
 
-        Ok(super::SolanaEvmBridgeResult {
+        Ok(Sent::Broadcast(super::SolanaEvmBridgeResult {
             attestation,
             burn: burn_hash,
-            recv: recv_hash,
-        })
+            recv: recv_sent.tx,
+            simulations,
+        }))
     }
 }
 
@@ -210,111 +317,218 @@ impl<SrcProvider: Provider<Ethereum> + WalletProvider + Clone, DstProvider: Sola
     pub fn new_evm_sol(
         source_provider: SrcProvider,
         destination_provider: DstProvider,
-        source_chain: NamedChain,
+        source_chain: impl Into<Chain>,
         recipient: Pubkey,
-        destination_chain: alloy_chains::Chain,
+        destination_chain: impl Into<Chain>,
     ) -> Self {
-        Self {
-            source_provider,
-            destination_provider,
-            source_chain: source_chain.into(),
-            destination_chain,
-            recipient: recipient.into(),
-            client: Client::new(),
-        }
+        CctpBuilder::new()
+            .with_source_provider(source_provider)
+            .with_destination_provider(destination_provider)
+            .assemble(
+                source_chain.into(),
+                destination_chain.into(),
+                recipient.into(),
+            )
     }
 
-    #[instrument(skip(self,signer,max_fee,destination_caller,min_finality_threshold), level = Level::INFO
-    )]
+    /// Burns on the EVM source chain and receives on Solana.
+    ///
+    /// `recipient_account` selects the USDC token account that is credited.
+    /// It is checked before burning, and created before receiving when
+    /// [`SolanaRecipientAccount::CreateAssociated`] is used. The `maxFee` is
+    /// the Iris fee of `mode`. A dry run returns the simulations of the
+    /// approval and the burn.
+    #[instrument(skip(self, signer, recipient_account, destination_caller), level = Level::INFO)]
     pub async fn bridge_evm_sol<S: Signer>(
         &self,
         signer: &S,
+        recipient_account: SolanaRecipientAccount<'_>,
         amount: alloy_primitives::U256,
         destination_caller: Option<EvmAddress>,
-        max_fee: Option<U256>,
-        min_finality_threshold: Option<u32>,
-    ) -> Result<super::EvmSolanaBridgeResult> {
+        mode: TransferMode,
+    ) -> Result<Sent<super::EvmSolanaBridgeResult>> {
         info!("burning {amount}");
-        let source_provider = self.source_provider();
-        let destination_provider = self.destination_provider();
         let recipient: Pubkey = self.recipient().try_into()?;
         let usdc_sol_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
-        let recipient_usdc_sol_address = spl_associated_token_account::get_associated_token_address(
-            &recipient,
-            &usdc_sol_address,
-        );
-        let recipient_bytes32: alloy_primitives::FixedBytes<32> =
-            recipient_usdc_sol_address.to_bytes().into();
-        let token_messenger: EvmAddress = self.token_messenger_contract()?.try_into()?;
-        let destination_domain = self.destination_domain_id()?;
-        let usdc_evm_address: EvmAddress = self.source_chain().usdc_token_address()?.try_into()?;
-        let usdc_sol_address: Pubkey = self.destination_chain().usdc_token_address()?.try_into()?;
-        let erc20 = ERC20::new(usdc_evm_address, source_provider);
-        let fees = self.get_fees().await?;
-        debug!("fees {fees}");
-        let usdc_balance = erc20
-            .balanceOf(source_provider.default_signer_address())
-            .call()
+        let (recipient_token_account, create_token_account) = recipient_account
+            .resolve(
+                self.destination_provider().rpc(),
+                &recipient,
+                &usdc_sol_address,
+            )
             .await?;
-        debug!("balance {usdc_balance}");
-
-        let current_allowance = erc20
-            .allowance(source_provider.default_signer_address(), token_messenger)
-            .call()
+        let usdc_balance = self
+            .source_provider()
+            .balance(self.source_chain(), &())
             .await?;
-        if current_allowance < 10 {
-            debug!("Approving allowance");
-            let approve_hash = erc20
-                .approve(token_messenger, U256::from(10))
-                .send()
-                .await?
-                .watch()
-                .await?;
-            info!("Approved USDC spending: {}", approve_hash);
+        debug!("balance {usdc_balance}");
+        if usdc_balance < amount {
+            return Err(Error::InsufficientBalance(usdc_balance, amount));
         }
-        let token_messenger = TokenMessengerContract::new(token_messenger, source_provider);
-        let burn_tx = token_messenger.deposit_for_burn_transaction(
-            source_provider.default_signer_address(),
-            recipient_bytes32,
-            destination_domain,
-            usdc_evm_address,
-            amount,
-            destination_caller.unwrap_or(EvmAddress::ZERO),
-            max_fee.unwrap_or(U256::from(3)),
-            min_finality_threshold.unwrap_or(0),
-        );
-
-        let burn_hash = source_provider
-            .send_transaction(burn_tx)
-            .await?
-            .with_required_confirmations(2)
-            .with_timeout(Some(Duration::from_secs(
-                self.source_chain().confirmation_average_time_seconds()?,
-            )))
-            .watch()
+        let sent = self
+            .burn_request(&BurnRequest {
+                amount,
+                destination_domain: self.destination_domain_id()?,
+                mint_recipient: Address::from(recipient_token_account).to_bytes32()?,
+                destination_caller: destination_caller.unwrap_or(EvmAddress::ZERO).into_word(),
+                max_fee: self.mode_fee(amount, mode).await?,
+                min_finality_threshold: mode.finality_threshold(),
+            })
             .await?;
+        let (burn_sent, approval) = match sent {
+            Sent::Broadcast(sent) => sent,
+            Sent::Simulated(simulations) => return Ok(Sent::Simulated(simulations)),
+        };
+        let mut simulations: Vec<_> = burn_sent.simulation.into_iter().collect();
+        let burn_hash = burn_sent.tx;
+        let burn = Some(burn_hash.into());
         let attestation = self
             .get_attestation_with_retry(
                 format!("0x{}", alloy_primitives::hex::encode(burn_hash)),
                 None,
-                Some(10),
+                None,
             )
-            .await?;
+            .await
+            .map_err(|e| e.at_stage(Stage::Attest, burn))?;
 
-        let recv_hash = recv::recv_message_internal(
-            signer,
-            destination_provider.rpc(),
-            attestation.clone(),
-            &usdc_sol_address,
-            usdc_evm_address,
-            self.source_chain().cctp_domain_id()?.to_string(),
-        )
-        .await?;
+        let recv_sent = match self
+            .solana_receive(
+                signer,
+                recipient_account.payer().unwrap_or(signer),
+                attestation.clone(),
+                &recipient_token_account,
+                create_token_account,
+            )
+            .await
+            .map_err(|e| e.at_stage(Stage::Receive, burn))?
+        {
+            Sent::Broadcast(recv_sent) => recv_sent,
+            Sent::Simulated(recv) => {
+                simulations.extend(recv);
+                return Ok(Sent::Simulated(simulations));
+            }
+        };
+        simulations.extend(recv_sent.simulation);
 
-        Ok(super::EvmSolanaBridgeResult {
+        Ok(Sent::Broadcast(super::EvmSolanaBridgeResult {
+            approval,
             attestation,
             burn: burn_hash,
-            recv: recv_hash,
+            recv: recv_sent.tx,
+            simulations,
+        }))
+    }
+}
+
+/// Solana transactions are signed by the given signer, which also pays the
+/// fees and the rent of the recipient's associated token account
+impl CctpEndpoint for SolanaWrapper {
+    type Signer = dyn Signer;
+    type TxId = Signature;
+
+    async fn balance(&self, chain: &Chain, signer: &Self::Signer) -> Result<U256> {
+        let usdc_address: Pubkey = chain.usdc_token_address()?.try_into()?;
+        let token_account = get_associated_token_address(&signer.pubkey(), &usdc_address);
+        let account = self
+            .rpc()
+            .get_account_with_commitment(&token_account, self.rpc().commitment())
+            .await?
+            .value;
+        match account {
+            Some(account) => {
+                let state =
+                    TokenAccount::unpack(&account.data).map_err(|e| Error::SolanaTokenAccount {
+                        account: token_account,
+                        reason: e.to_string(),
+                    })?;
+                Ok(U256::from(state.amount))
+            }
+            None => Ok(U256::ZERO),
+        }
+    }
+
+    /// The recipient's associated token account
+    async fn mint_recipient<S>(
+        cctp: &Cctp<S, Self>,
+        recipient: &Address,
+    ) -> Result<FixedBytes<32>> {
+        let recipient: Pubkey = recipient.try_into()?;
+        let usdc_address: Pubkey = cctp.destination_chain().usdc_token_address()?.try_into()?;
+        Ok(get_associated_token_address(&recipient, &usdc_address)
+            .to_bytes()
+            .into())
+    }
+
+    async fn burn<D>(
+        cctp: &Cctp<Self, D>,
+        signer: &Self::Signer,
+        request: &BurnRequest,
+    ) -> Result<Sent<SentTx<Signature>>> {
+        let event_account = Keypair::new();
+        cctp.solana_burn(
+            signer.pubkey(),
+            event_account.pubkey(),
+            &[signer, &event_account as &dyn Signer],
+            request,
+        )
+        .await
+    }
+
+    async fn receive<S>(
+        cctp: &Cctp<S, Self>,
+        signer: &Self::Signer,
+        attestation: &Attestation,
+    ) -> Result<Sent<SentTx<Signature>>> {
+        let recipient: Pubkey = cctp.recipient().try_into()?;
+        let usdc_address: Pubkey = cctp.destination_chain().usdc_token_address()?.try_into()?;
+        let (token_account, create) = SolanaRecipientAccount::CreateAssociated { payer: None }
+            .resolve(cctp.destination_provider().rpc(), &recipient, &usdc_address)
+            .await?;
+        cctp.solana_receive(signer, signer, attestation.clone(), &token_account, create)
+            .await
+    }
+
+    async fn is_received(&self, chain: &Chain, attestation: &Attestation) -> Result<bool> {
+        let nonce = attestation
+            .nonce()
+            .ok_or_else(|| Error::AttestationFailed {
+                reason: "message too short".to_string(),
+            })?;
+        let message_transmitter: Pubkey = chain.message_transmitter_address()?.try_into()?;
+        let (used_nonce, _) = Pubkey::find_program_address(
+            &[USED_NONCE_SEED, nonce.as_slice()],
+            &message_transmitter,
+        );
+        Ok(self
+            .rpc()
+            .get_account_with_commitment(&used_nonce, self.rpc().commitment())
+            .await?
+            .value
+            .is_some())
+    }
+
+    async fn execution(&self, _chain: &Chain, tx: &Signature) -> Result<TxExecution> {
+        let tx = self
+            .rpc()
+            .get_transaction(tx, UiTransactionEncoding::Base64)
+            .await?;
+        Ok(TxExecution {
+            timestamp: tx.block_time.and_then(|time| u64::try_from(time).ok()),
+            gas_used: tx
+                .transaction
+                .meta
+                .and_then(|meta| meta.compute_units_consumed.into()),
         })
     }
+
+    async fn burn_costs<D>(
+        cctp: &Cctp<Self, D>,
+        _request: &BurnRequest,
+    ) -> Result<Vec<CostEstimate>> {
+        cctp.solana_burn_costs().await
+    }
+
+    async fn receive_costs<S>(cctp: &Cctp<S, Self>) -> Result<Vec<CostEstimate>> {
+        cctp.solana_receive_costs().await
+    }
 }
@@ -0,0 +1,266 @@
+use {
+    super::{
+        Cctp,
+        ConfirmationPolicy,
+        Operation,
+        Sent,
+        SentTx,
+        SimulationMode,
+        confirmation::wait_for_commitment,
+        simulation::simulate_solana,
+    },
+    crate::{Error, Result},
+    alloy_chains::Chain,
+    solana_compute_budget_interface::ComputeBudgetInstruction,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        client_error::{Error as ClientError, ErrorKind as ClientErrorKind},
+        request::{RpcError, RpcResponseErrorData},
+    },
+    solana_signature::Signature,
+    solana_signer::signers::Signers,
+    solana_transaction::Transaction,
+    std::time::Duration,
+    tracing::debug,
+};
+
+/// Most accounts `getRecentPrioritizationFees` accepts
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Compute unit price, in micro-lamports, of the Solana transactions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// No priority fee
+    #[default]
+    None,
+    /// A fixed price
+    Fixed(u64),
+    /// The `percentile` of the fees paid in recent slots for the writable
+    /// accounts of the transaction, reported by `getRecentPrioritizationFees`,
+    /// capped at `max` when set
+    Estimate { percentile: u8, max: Option<u64> },
+}
+
+/// Compute budget applied to every Solana transaction sent by [`Cctp`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolanaTxConfig {
+    /// Compute unit limit, `None` for the runtime default
+    pub compute_unit_limit: Option<u32>,
+    /// Compute unit price
+    pub priority_fee: PriorityFee,
+}
+
+impl SolanaTxConfig {
+    /// Resolves the compute unit price for a transaction writing `accounts`
+    pub async fn compute_unit_price(&self, rpc: &RpcClient, accounts: &[Pubkey]) -> Result<u64> {
+        match self.priority_fee {
+            PriorityFee::None => Ok(0),
+            PriorityFee::Fixed(price) => Ok(price),
+            PriorityFee::Estimate { percentile, max } => {
+                let accounts = &accounts[..accounts.len().min(MAX_PRIORITIZATION_FEE_ACCOUNTS)];
+                let fees = rpc
+                    .get_recent_prioritization_fees(accounts)
+                    .await?
+                    .into_iter()
+                    .map(|fee| fee.prioritization_fee)
+                    .collect();
+                let price = fee_percentile(fees, percentile);
+                debug!("estimated priority fee {price} (p{percentile})");
+                Ok(max.map_or(price, |max| price.min(max)))
+            }
+        }
+    }
+
+    /// Compute budget instructions to prepend to `instructions`
+    pub async fn compute_budget_instructions(
+        &self,
+        rpc: &RpcClient,
+        instructions: &[Instruction],
+    ) -> Result<Vec<Instruction>> {
+        let mut budget = Vec::with_capacity(2);
+        if let Some(limit) = self.compute_unit_limit {
+            budget.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        let price = self
+            .compute_unit_price(rpc, &writable_accounts(instructions))
+            .await?;
+        if price > 0 {
+            budget.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        Ok(budget)
+    }
+}
+
+/// The `percentile` of `fees`, `0` when there are none
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index]
+}
+
+/// The distinct writable accounts of `instructions`
+fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts
+}
+
+/// How [`send_instructions`] builds, simulates and confirms one transaction
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SolanaSend {
+    pub(crate) config: SolanaTxConfig,
+    pub(crate) confirmation: ConfirmationPolicy,
+    pub(crate) timeout: Duration,
+    pub(crate) simulation: SimulationMode,
+    pub(crate) operation: Operation,
+}
+
+impl<SrcProvider, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// Returns the [`SolanaTxConfig`] applied to Solana transactions
+    pub fn solana_tx_config(&self) -> &SolanaTxConfig {
+        &self.solana_tx_config
+    }
+
+    /// The [`SolanaSend`] of `operation` transactions sent on `chain`
+    pub(crate) fn solana_send(&self, chain: &Chain, operation: Operation) -> SolanaSend {
+        let (confirmation, timeout) = self.confirmation_policy(chain, operation);
+        SolanaSend {
+            config: self.solana_tx_config,
+            confirmation,
+            timeout,
+            simulation: self.simulation,
+            operation,
+        }
+    }
+
+    /// Sets the [`SolanaTxConfig`] applied to Solana transactions
+    pub fn with_solana_tx_config(mut self, config: SolanaTxConfig) -> Self {
+        self.solana_tx_config = config;
+        self
+    }
+}
+
+/// Sends `instructions` in one transaction paid by `payer`, preceded by the
+/// compute budget instructions of `send`, and waits for its confirmation
+/// policy. The signed transaction is simulated first unless disabled
+pub(crate) async fn send_instructions<S: Signers + ?Sized>(
+    rpc: &RpcClient,
+    send: &SolanaSend,
+    instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    signers: &S,
+) -> Result<Sent<SentTx<Signature>>> {
+    let mut tx = build_transaction(rpc, send, instructions, payer).await?;
+    tx.try_sign(signers, tx.message.recent_blockhash)?;
+    submit_transaction(rpc, send, &tx).await
+}
+
+/// The unsigned transaction of `instructions` paid by `payer`, preceded by
+/// the compute budget instructions of `send`, with the latest blockhash
+pub(crate) async fn build_transaction(
+    rpc: &RpcClient,
+    send: &SolanaSend,
+    instructions: Vec<Instruction>,
+    payer: &Pubkey,
+) -> Result<Transaction> {
+    let mut all = send
+        .config
+        .compute_budget_instructions(rpc, &instructions)
+        .await?;
+    all.extend(instructions);
+    let mut tx = Transaction::new_with_payer(&all, Some(payer));
+    tx.message.recent_blockhash = rpc.get_latest_blockhash().await?;
+    Ok(tx)
+}
+
+/// Whether `error` means the transaction was rejected, or failed, and cannot
+/// land anymore
+fn rejected(error: &ClientError) -> bool {
+    matches!(
+        error.kind(),
+        ClientErrorKind::TransactionError(_)
+            | ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(_),
+                ..
+            })
+    )
+}
+
+/// Simulates the signed `tx` unless disabled, sends it and waits for the
+/// confirmation policy of `send`. A dry run only simulates it
+pub(crate) async fn submit_transaction(
+    rpc: &RpcClient,
+    send: &SolanaSend,
+    tx: &Transaction,
+) -> Result<Sent<SentTx<Signature>>> {
+    let simulation = match send.simulation {
+        SimulationMode::Disabled => None,
+        SimulationMode::Enabled => Some(simulate_solana(rpc, send.operation, tx).await?),
+        SimulationMode::DryRun => {
+            return Ok(Sent::Simulated(vec![
+                simulate_solana(rpc, send.operation, tx).await?,
+            ]));
+        }
+    };
+    let signature = tx.signatures[0];
+    rpc.send_and_confirm_transaction(tx)
+        .await
+        .map_err(|e| match rejected(&e) {
+            true => Error::from(e).decode_revert(),
+            false => Error::from(e).after_broadcast(send.operation, signature),
+        })?;
+    wait_for_commitment(rpc, send.confirmation, &signature, send.timeout)
+        .await
+        .map_err(|e| e.after_broadcast(send.operation, signature))?;
+    Ok(Sent::Broadcast(SentTx {
+        tx: signature,
+        simulation,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest, solana_instruction::AccountMeta};
+
+    #[rstest]
+    #[case(vec![], 50, 0)]
+    #[case(vec![7], 90, 7)]
+    #[case(vec![0, 0, 10, 20, 30], 0, 0)]
+    #[case(vec![30, 0, 20, 0, 10], 50, 10)]
+    #[case(vec![0, 0, 10, 20, 30], 75, 20)]
+    #[case(vec![0, 0, 10, 20, 30], 100, 30)]
+    #[case(vec![0, 0, 10, 20, 30], 200, 30)]
+    fn test_fee_percentile(#[case] fees: Vec<u64>, #[case] percentile: u8, #[case] expected: u64) {
+        assert_eq!(fee_percentile(fees, percentile), expected);
+    }
+
+    #[test]
+    fn test_writable_accounts() {
+        let program = Pubkey::new_unique();
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let instructions = [
+            Instruction::new_with_bytes(program, &[], vec![
+                AccountMeta::new(a, true),
+                AccountMeta::new_readonly(b, false),
+            ]),
+            Instruction::new_with_bytes(program, &[], vec![
+                AccountMeta::new(c, false),
+                AccountMeta::new(a, false),
+            ]),
+        ];
+        assert_eq!(writable_accounts(&instructions), vec![a, c]);
+    }
+}
@@ -0,0 +1,193 @@
+use {
+    super::solana_tx::{SolanaSend, send_instructions},
+    crate::{Error, Result},
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    spl_associated_token_account::{
+        get_associated_token_address,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token::{solana_program::program_pack::Pack, state::Account as TokenAccount},
+    std::fmt::Debug,
+    tracing::{debug, info},
+};
+
+/// Selects the USDC token account credited on Solana when bridging from EVM
+#[derive(Clone, Copy, Default)]
+pub enum SolanaRecipientAccount<'a> {
+    /// The recipient's associated token account, which must already exist
+    #[default]
+    Associated,
+    /// The recipient's associated token account, created before receiving if
+    /// it does not exist. Rent is paid by `payer`, or by the receiving signer
+    /// when `None`
+    CreateAssociated { payer: Option<&'a dyn Signer> },
+    /// An existing token account of the chain's USDC mint, owned by the
+    /// recipient
+    TokenAccount(Pubkey),
+}
+
+impl Debug for SolanaRecipientAccount<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Associated => write!(f, "Associated"),
+            Self::CreateAssociated { payer } => {
+                write!(
+                    f,
+                    "CreateAssociated(payer: {:?})",
+                    payer.map(|p| p.pubkey())
+                )
+            }
+            Self::TokenAccount(account) => write!(f, "TokenAccount({account})"),
+        }
+    }
+}
+
+impl<'a> SolanaRecipientAccount<'a> {
+    /// Returns the configured payer for account creation, if any
+    pub fn payer(&self) -> Option<&'a dyn Signer> {
+        match self {
+            Self::CreateAssociated { payer } => *payer,
+            _ => None,
+        }
+    }
+
+    /// Resolves the token account for `owner` and checks it holds `mint`.
+    ///
+    /// Returns the token account address and whether it still needs to be
+    /// created.
+    pub async fn resolve(
+        &self,
+        rpc: &RpcClient,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(Pubkey, bool)> {
+        let address = match self {
+            Self::Associated | Self::CreateAssociated { .. } => {
+                get_associated_token_address(owner, mint)
+            }
+            Self::TokenAccount(address) => *address,
+        };
+        let account = rpc
+            .get_account_with_commitment(&address, rpc.commitment())
+            .await?
+            .value;
+        match (account, self) {
+            (Some(account), _) => {
+                check_token_account(&address, &account.owner, &account.data, owner, mint)?;
+                debug!("using token account {address}");
+                Ok((address, false))
+            }
+            (None, Self::CreateAssociated { .. }) => {
+                debug!("token account {address} will be created");
+                Ok((address, true))
+            }
+            (None, _) => Err(Error::SolanaTokenAccount {
+                account: address,
+                reason: "account does not exist".to_string(),
+            }),
+        }
+    }
+}
+
+/// Checks that `data` is an SPL token account for `mint` owned by `owner`
+fn check_token_account(
+    address: &Pubkey,
+    program_owner: &Pubkey,
+    data: &[u8],
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    let invalid = |reason: String| Error::SolanaTokenAccount {
+        account: *address,
+        reason,
+    };
+    if *program_owner != spl_token::id() {
+        return Err(invalid(format!(
+            "not owned by the token program: {program_owner}"
+        )));
+    }
+    let state = TokenAccount::unpack(data).map_err(|e| invalid(e.to_string()))?;
+    if state.mint != *mint {
+        return Err(invalid(format!("mint {} is not {mint}", state.mint)));
+    }
+    if state.owner != *owner {
+        return Err(invalid(format!("owner {} is not {owner}", state.owner)));
+    }
+    Ok(())
+}
+
+/// Creates the associated token account of `owner` for `mint`, paid by `payer`
+pub(crate) async fn create_associated_token_account<T: Signer + ?Sized>(
+    rpc: &RpcClient,
+    send: &SolanaSend,
+    payer: &T,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Signature> {
+    let instruction =
+        create_associated_token_account_idempotent(&payer.pubkey(), owner, mint, &spl_token::id());
+    let signature = send_instructions(rpc, send, vec![instruction], &payer.pubkey(), &[payer])
+        .await?
+        .broadcast()?
+        .tx;
+    info!("created token account for {owner}: {signature}");
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, spl_token::state::AccountState};
+
+    fn token_account_data(mint: Pubkey, owner: Pubkey) -> Vec<u8> {
+        let state = TokenAccount {
+            mint,
+            owner,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(state, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_check_token_account() {
+        let address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let data = token_account_data(mint, owner);
+
+        assert!(check_token_account(&address, &spl_token::id(), &data, &owner, &mint).is_ok());
+        assert!(matches!(
+            check_token_account(&address, &Pubkey::new_unique(), &data, &owner, &mint),
+            Err(Error::SolanaTokenAccount { .. })
+        ));
+        assert!(matches!(
+            check_token_account(
+                &address,
+                &spl_token::id(),
+                &data,
+                &owner,
+                &Pubkey::new_unique()
+            ),
+            Err(Error::SolanaTokenAccount { .. })
+        ));
+        assert!(matches!(
+            check_token_account(
+                &address,
+                &spl_token::id(),
+                &data,
+                &Pubkey::new_unique(),
+                &mint
+            ),
+            Err(Error::SolanaTokenAccount { .. })
+        ));
+        assert!(matches!(
+            check_token_account(&address, &spl_token::id(), &[0; 3], &owner, &mint),
+            Err(Error::SolanaTokenAccount { .. })
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use {
+    super::{
+        BridgeResult,
+        BurnRequest,
+        Cctp,
+        CctpEndpoint,
+        Operation,
+        Sent,
+        SimulatedCost,
+        Simulation,
+        SimulationMode,
+        confirmation::wait_for_block_tag,
+    },
+    crate::{
+        Bundler,
+        Error,
+        IEntryPoint,
+        Result,
+        SmartAccount,
+        Stage,
+        UserOperation,
+        UserOperationReceipt,
+    },
+    alloy_network::Ethereum,
+    alloy_primitives::{TxHash, U256, aliases::U192},
+    alloy_provider::Provider,
+    std::time::{Duration, Instant},
+    tracing::{Level, debug, info, instrument},
+};
+
+/// How often the bundler is asked for the receipt of a sent operation
+pub const USER_OPERATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// Burns from ERC-4337 smart accounts
+impl<SrcProvider: Provider<Ethereum>, DstProvider> Cctp<SrcProvider, DstProvider> {
+    /// The signed operation of `account` executing the calls of
+    /// [`Cctp::safe_burn_calls`] for `request`, with gas estimated by
+    /// `bundler`
+    pub async fn user_operation_burn<A: SmartAccount, B: Bundler>(
+        &self,
+        account: &A,
+        bundler: &B,
+        request: &BurnRequest,
+    ) -> Result<(UserOperation, Simulation)> {
+        let provider = self.source_provider();
+        let sender = account.address();
+        let entry_point = account.entry_point();
+        let calls = self.safe_burn_calls(sender, request).await?;
+        let nonce = IEntryPoint::new(entry_point, provider)
+            .getNonce(sender, U192::ZERO)
+            .call()
+            .await?;
+        let (max_fee, priority_fee) = self
+            .gas_policy(self.source_chain())
+            .fees(provider.estimate_eip1559_fees().await?);
+        let (factory, factory_data) = account.factory().unzip();
+        let op = UserOperation {
+            sender,
+            nonce,
+            factory,
+            factory_data,
+            call_data: account.execute_batch(&calls),
+            max_fee_per_gas: U256::from(max_fee),
+            max_priority_fee_per_gas: U256::from(priority_fee),
+            signature: account.dummy_signature(),
+            ..Default::default()
+        };
+        let gas = bundler
+            .estimate_user_operation_gas(&op, entry_point)
+            .await?;
+        let simulation = Simulation {
+            operation: Operation::Burn,
+            cost: SimulatedCost::Gas(gas.total().saturating_to()),
+        };
+        debug!("estimated user operation {simulation}");
+        let mut op = op.with_gas(&gas);
+        let hash = op.hash(entry_point, provider.get_chain_id().await?);
+        op.signature = account.sign_user_operation(hash).await?;
+        Ok((op, simulation))
+    }
+
+    /// Sends the burn of `request` as an operation of `account` and waits
+    /// until a bundle transaction includes it. Pass the
+    /// [`BundleReceipt::transaction_hash`](crate::BundleReceipt) to
+    /// [`Cctp::resume_bridge`] to attest and receive. A dry run returns the
+    /// bundler's gas estimate of the whole operation
+    #[instrument(skip(self, account, bundler), level = Level::INFO)]
+    pub async fn send_user_operation_burn<A: SmartAccount, B: Bundler>(
+        &self,
+        account: &A,
+        bundler: &B,
+        request: &BurnRequest,
+    ) -> Result<Sent<UserOperationReceipt>> {
+        let send = self.evm_send(self.source_chain(), Operation::Burn);
+        let receipt = async {
+            let (op, simulation) = self.user_operation_burn(account, bundler, request).await?;
+            if self.simulation == SimulationMode::DryRun {
+                return Ok(Sent::Simulated(vec![simulation]));
+            }
+            let user_op_hash = bundler
+                .send_user_operation(&op, account.entry_point())
+                .await?;
+            info!("sent user operation {user_op_hash}");
+            let started = Instant::now();
+            let receipt = async {
+                loop {
+                    if let Some(receipt) = bundler.user_operation_receipt(user_op_hash).await? {
+                        return Ok(receipt);
+                    }
+                    if started.elapsed() > send.timeout {
+                        return Err(Error::UserOperationTimeout {
+                            user_op_hash,
+                            timeout: send.timeout,
+                        });
+                    }
+                    tokio::time::sleep(USER_OPERATION_POLL_INTERVAL).await;
+                }
+            }
+            .await
+            .map_err(|e| Error::Broadcast {
+                operation: Operation::Burn,
+                tx: user_op_hash.to_string(),
+                source: Box::new(e),
+            })?;
+            if !receipt.success {
+                return Err(Error::UserOperationFailed {
+                    user_op_hash,
+                    reason: receipt.reason.unwrap_or_default(),
+                });
+            }
+            Ok(Sent::Broadcast(receipt))
+        }
+        .await
+        .map_err(|e| e.at_stage(Stage::Burn, None))?;
+        let receipt = match receipt {
+            Sent::Broadcast(receipt) => receipt,
+            simulated => return Ok(simulated),
+        };
+        let tx = receipt.receipt.transaction_hash;
+        info!("burn {tx}");
+        wait_for_block_tag(self.source_provider(), send.confirmation, tx, send.timeout)
+            .await
+            .map_err(|e| e.after_broadcast(Operation::Burn, tx))?;
+        Ok(Sent::Broadcast(receipt))
+    }
+}
+
+impl<SrcProvider: Provider<Ethereum>, DstProvider: CctpEndpoint> Cctp<SrcProvider, DstProvider> {
+    /// Like [`Cctp::bridge_request`], burning from the smart `account`
+    /// through `bundler`
+    #[instrument(skip(self, account, bundler, destination_signer), level = Level::INFO)]
+    pub async fn bridge_user_operation<A: SmartAccount, B: Bundler>(
+        &self,
+        account: &A,
+        bundler: &B,
+        request: &BurnRequest,
+        destination_signer: &DstProvider::Signer,
+    ) -> Result<Sent<BridgeResult<TxHash, DstProvider::TxId>>> {
+        let receipt = match self
+            .send_user_operation_burn(account, bundler, request)
+            .await?
+        {
+            Sent::Broadcast(receipt) => receipt,
+            Sent::Simulated(simulations) => return Ok(Sent::Simulated(simulations)),
+        };
+        self.resume_bridge(receipt.receipt.transaction_hash, destination_signer)
+            .await
+    }
+}
@@ -0,0 +1,254 @@
+//! [CAIP-2](https://chainagnostic.org/CAIPs/caip-2) chain ids and
+//! [CAIP-10](https://chainagnostic.org/CAIPs/caip-10) account ids, as used by
+//! Reown/WalletConnect.
+use {
+    crate::{
+        APTOS_ID,
+        APTOS_TESTNET_ID,
+        Address,
+        AddressKind,
+        NOBLE_ID,
+        NOBLE_TESTNET_ID,
+        SOLANA_DEVNET_ID,
+        SOLANA_MAINNET_ID,
+        SUI_ID,
+        SUI_TESTNET_ID,
+        error::Error,
+    },
+    alloy_chains::Chain,
+    std::{
+        fmt::{Display, Formatter},
+        str::FromStr,
+    },
+};
+
+/// CAIP-2 namespace of EVM chains
+pub const EIP155_NAMESPACE: &str = "eip155";
+/// CAIP-2 namespace of Solana clusters
+pub const SOLANA_NAMESPACE: &str = "solana";
+/// CAIP-2 namespace of Cosmos chains such as Noble
+pub const COSMOS_NAMESPACE: &str = "cosmos";
+/// CAIP-2 namespace of Sui networks
+pub const SUI_NAMESPACE: &str = "sui";
+/// CAIP-2 namespace of Aptos networks
+pub const APTOS_NAMESPACE: &str = "aptos";
+
+/// CAIP-2 namespace and reference of the crate's non EVM [`Chain`]s, with the
+/// kind of their accounts
+const NON_EVM_CHAINS: &[(u64, &str, &str, AddressKind)] = &[
+    (
+        SOLANA_MAINNET_ID,
+        SOLANA_NAMESPACE,
+        "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp",
+        AddressKind::Solana,
+    ),
+    (
+        SOLANA_DEVNET_ID,
+        SOLANA_NAMESPACE,
+        "EtWTRABZaYq6iMfeYKouRu166VU2xqa1",
+        AddressKind::Solana,
+    ),
+    (NOBLE_ID, COSMOS_NAMESPACE, "noble-1", AddressKind::Noble),
+    (
+        NOBLE_TESTNET_ID,
+        COSMOS_NAMESPACE,
+        "grand-1",
+        AddressKind::Noble,
+    ),
+    (SUI_ID, SUI_NAMESPACE, "mainnet", AddressKind::Move),
+    (SUI_TESTNET_ID, SUI_NAMESPACE, "testnet", AddressKind::Move),
+    (APTOS_ID, APTOS_NAMESPACE, "1", AddressKind::Move),
+    (APTOS_TESTNET_ID, APTOS_NAMESPACE, "2", AddressKind::Move),
+];
+
+fn invalid(id: &str, reason: impl Display) -> Error {
+    Error::InvalidCaip(format!("{id}: {reason}"))
+}
+
+/// A CAIP-2 chain id, e.g. `eip155:8453` or
+/// `solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Caip2(Chain);
+
+impl Caip2 {
+    pub fn chain(&self) -> Chain {
+        self.0
+    }
+
+    pub fn is_solana(&self) -> bool {
+        self.address_kind() == AddressKind::Solana
+    }
+
+    /// The kind of the accounts of the chain
+    pub fn address_kind(&self) -> AddressKind {
+        non_evm(&self.0).map_or(AddressKind::Evm, |(_, _, _, kind)| *kind)
+    }
+}
+
+fn non_evm(chain: &Chain) -> Option<&'static (u64, &'static str, &'static str, AddressKind)> {
+    NON_EVM_CHAINS.iter().find(|(id, ..)| *id == chain.id())
+}
+
+impl From<Chain> for Caip2 {
+    fn from(chain: Chain) -> Self {
+        Self(chain)
+    }
+}
+
+impl From<Caip2> for Chain {
+    fn from(caip2: Caip2) -> Self {
+        caip2.0
+    }
+}
+
+impl Display for Caip2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match non_evm(&self.0) {
+            Some((_, namespace, reference, _)) => write!(f, "{namespace}:{reference}"),
+            None => write!(f, "{EIP155_NAMESPACE}:{}", self.0.id()),
+        }
+    }
+}
+
+impl FromStr for Caip2 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, reference) = s
+            .split_once(':')
+            .ok_or_else(|| invalid(s, "expected namespace:reference"))?;
+        match namespace {
+            EIP155_NAMESPACE => {
+                let id: u64 = reference.parse().map_err(|e| invalid(s, e))?;
+                Ok(Self(Chain::from_id(id)))
+            }
+            SOLANA_NAMESPACE | COSMOS_NAMESPACE | SUI_NAMESPACE | APTOS_NAMESPACE => NON_EVM_CHAINS
+                .iter()
+                .find(|(_, known_namespace, known, _)| {
+                    *known_namespace == namespace && *known == reference
+                })
+                .map(|(id, ..)| Self(Chain::from_id_unchecked(*id)))
+                .ok_or_else(|| invalid(s, format!("unknown {namespace} chain"))),
+            _ => Err(invalid(s, format!("unsupported namespace {namespace}"))),
+        }
+    }
+}
+
+/// A CAIP-10 account id, e.g.
+/// `eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Caip10 {
+    chain: Caip2,
+    address: Address,
+}
+
+impl Caip10 {
+    /// Fails when `address` is not an account of `chain`'s namespace
+    pub fn new(chain: impl Into<Caip2>, address: impl Into<Address>) -> Result<Self, Error> {
+        let chain = chain.into();
+        let address = address.into();
+        let address = address.with_kind(chain.address_kind()).map_err(|_| {
+            invalid(
+                &format!("{chain}:{address}"),
+                "address does not match the chain namespace",
+            )
+        })?;
+        Ok(Self { chain, address })
+    }
+
+    pub fn chain(&self) -> Chain {
+        self.chain.chain()
+    }
+
+    pub fn caip2(&self) -> Caip2 {
+        self.chain
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+impl From<Caip10> for Address {
+    fn from(caip10: Caip10) -> Self {
+        caip10.address
+    }
+}
+
+impl Display for Caip10 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.chain, self.address)
+    }
+}
+
+impl FromStr for Caip10 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain, account) = s
+            .rsplit_once(':')
+            .ok_or_else(|| invalid(s, "expected namespace:reference:address"))?;
+        let chain: Caip2 = chain.parse()?;
+        let address: Address = account.parse().map_err(|e| invalid(s, e))?;
+        Self::new(chain, address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{APTOS, LINEA_ID, NOBLE, SOLANA_DEVNET, SOLANA_MAINNET, SUI_TESTNET},
+        alloy_chains::NamedChain,
+        rstest::rstest,
+    };
+
+    #[rstest]
+    #[case(NamedChain::Mainnet.into(), "eip155:1")]
+    #[case(NamedChain::Base.into(), "eip155:8453")]
+    #[case(NamedChain::ArbitrumSepolia.into(), "eip155:421614")]
+    #[case(Chain::from_id(LINEA_ID), "eip155:59144")]
+    #[case(SOLANA_MAINNET, "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp")]
+    #[case(SOLANA_DEVNET, "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1")]
+    #[case(NOBLE, "cosmos:noble-1")]
+    #[case(SUI_TESTNET, "sui:testnet")]
+    #[case(APTOS, "aptos:1")]
+    fn test_caip2(#[case] chain: Chain, #[case] caip2: &str) -> anyhow::Result<()> {
+        assert_eq!(Caip2::from(chain).to_string(), caip2);
+        assert_eq!(caip2.parse::<Caip2>()?.chain(), chain);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("eip155")]
+    #[case("eip155:base")]
+    #[case("solana:4uhcVJyU9pJkvQyS88uRDiswHXSCkY3z")]
+    #[case("cosmos:cosmoshub-4")]
+    #[case("sui:devnet")]
+    fn test_invalid_caip2(#[case] caip2: &str) {
+        assert!(matches!(caip2.parse::<Caip2>(), Err(Error::InvalidCaip(_))));
+    }
+
+    #[rstest]
+    #[case("eip155:8453:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb")]
+    #[case("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")]
+    #[case("cosmos:noble-1:noble1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5hpek7j")]
+    #[case("sui:mainnet:0x5d4b302506645c37ff133b98c4b50a5ae14841659738d6d733d59d0d217a93bf")]
+    fn test_caip10_roundtrip(#[case] caip10: &str) -> anyhow::Result<()> {
+        let account: Caip10 = caip10.parse()?;
+        assert_eq!(account.to_string(), caip10);
+        assert_eq!(Caip10::new(account.chain(), account.address())?, account);
+        Ok(())
+    }
+
+    #[test]
+    fn test_caip10_mismatched_address() {
+        let pubkey = solana_pubkey::Pubkey::new_unique();
+        assert!(Caip10::new(Chain::from(NamedChain::Base), pubkey).is_err());
+        assert!(
+            "eip155:1:EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+                .parse::<Caip10>()
+                .is_err()
+        );
+    }
+}
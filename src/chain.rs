@@ -1,52 +1,36 @@
 use {
     crate::{
-        ARBITRUM_DOMAIN_ID,
-        ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS,
-        ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-        AVALANCHE_DOMAIN_ID,
-        AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS,
-        BASE_DOMAIN_ID,
-        BASE_MESSAGE_TRANSMITTER_ADDRESS,
-        BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-        BASE_TOKEN_MESSENGER_ADDRESS,
-        ETHEREUM_DOMAIN_ID,
-        ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS,
-        ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-        ETHEREUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS,
-        ETHEREUM_TOKEN_MESSENGER_ADDRESS,
-        OPTIMISM_DOMAIN_ID,
-        OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS,
-        OPTIMISM_TOKEN_MESSENGER_ADDRESS,
-        POLYGON_CCTP_MESSAGE_TRANSMITTER,
-        POLYGON_CCTP_TOKEN_MESSENGER,
-        POLYGON_DOMAIN_ID,
-        SOLANA_DEVNET_ID,
-        SOLANA_DEVNET_USDC_TOKEN,
-        SOLANA_DOMAIN_ID,
-        SOLANA_MAINNET_ID,
-        SOLANA_MAINNET_USDC_TOKEN,
-        UNICHAIN_CCTP_MESSAGE_TRANSMITTER,
-        UNICHAIN_CCTP_TOKEN_MESSENGER,
-        UNICHAIN_DOMAIN_ID,
+        APTOS_DOMAIN_ID,
+        APTOS_ID,
+        APTOS_TESTNET_ID,
+        Domain,
+        NOBLE_DOMAIN_ID,
+        NOBLE_ID,
+        NOBLE_TESTNET_ID,
+        SUI_DOMAIN_ID,
+        SUI_ID,
+        SUI_TESTNET_ID,
         address::Address,
         error::{Error, Result},
-        token_messenger::*,
+        registry::{ChainConfig, chain_config},
     },
     alloy_chains::{Chain, ChainKind, NamedChain},
 };
 
-/// Trait for chains that support CCTP bridging
+/// Trait for chains that support CCTP bridging, backed by the global
+/// [`ChainRegistry`](crate::ChainRegistry)
 pub trait CctpChain {
     /// The average time to confirmation of the chain, according to the CCTP docs: <https://developers.circle.com/stablecoins/required-block-confirmations>
     fn confirmation_average_time_seconds(&self) -> Result<u64>;
     /// The domain ID of the chain - used to identify the chain when bridging: <https://developers.circle.com/stablecoins/evm-smart-contracts>
-    fn cctp_domain_id(&self) -> Result<u32>;
+    fn cctp_domain_id(&self) -> Result<Domain>;
     /// The address of the `TokenMessenger` contract on the chain
     fn token_messenger_address(&self) -> Result<Address>;
     /// The address of the `MessageTransmitter` contract on the chain
     fn message_transmitter_address(&self) -> Result<Address>;
 
-    /// Check if the chain is supported for CCTP
+    /// Check if the chain is supported for CCTP, i.e. its registry entry is
+    /// complete
     fn is_supported(&self) -> bool;
 
     fn sandbox(&self) -> bool;
@@ -54,229 +38,98 @@ pub trait CctpChain {
     fn usdc_token_address(&self) -> Result<Address>;
 }
 
+/// Reads `field` of the registry entry of `chain`
+fn lookup<T>(chain: &Chain, field: impl FnOnce(&ChainConfig) -> Option<T>) -> Result<T> {
+    chain_config(chain)
+        .as_ref()
+        .and_then(field)
+        .ok_or_else(|| Error::ChainNotSupported {
+            chain: chain.to_string(),
+        })
+}
+
+/// Non EVM chains CCTP can burn to but the crate cannot call, only their
+/// domain is known: chain id, domain id and sandbox
+const BURN_ONLY_CHAINS: &[(u64, u32, bool)] = &[
+    (NOBLE_ID, NOBLE_DOMAIN_ID, false),
+    (NOBLE_TESTNET_ID, NOBLE_DOMAIN_ID, true),
+    (SUI_ID, SUI_DOMAIN_ID, false),
+    (SUI_TESTNET_ID, SUI_DOMAIN_ID, true),
+    (APTOS_ID, APTOS_DOMAIN_ID, false),
+    (APTOS_TESTNET_ID, APTOS_DOMAIN_ID, true),
+];
+
+fn burn_only(chain: &Chain) -> Option<&'static (u64, u32, bool)> {
+    BURN_ONLY_CHAINS.iter().find(|(id, ..)| *id == chain.id())
+}
+
 impl CctpChain for Chain {
     fn sandbox(&self) -> bool {
-        match self.kind() {
-            ChainKind::Named(n) => n.sandbox(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID => true,
-                SOLANA_MAINNET_ID => false,
-                _ => false,
-            },
-        }
+        chain_config(self)
+            .and_then(|config| config.sandbox)
+            .or_else(|| burn_only(self).map(|(_, _, sandbox)| *sandbox))
+            .unwrap_or_else(|| match self.kind() {
+                ChainKind::Named(n) => n.is_testnet(),
+                ChainKind::Id(_) => false,
+            })
     }
 
     fn confirmation_average_time_seconds(&self) -> Result<u64> {
-        match self.kind() {
-            ChainKind::Named(n) => n.confirmation_average_time_seconds(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID => Ok(4),
-                SOLANA_MAINNET_ID => Ok(4),
-                _ => Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                }),
-            },
-        }
+        lookup(self, |config| config.confirmation_average_time_seconds)
     }
 
-    fn cctp_domain_id(&self) -> Result<u32> {
-        match self.kind() {
-            ChainKind::Named(n) => n.cctp_domain_id(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID => Ok(SOLANA_DOMAIN_ID),
-                SOLANA_MAINNET_ID => Ok(SOLANA_DOMAIN_ID),
-                _ => Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                }),
-            },
-        }
+    fn cctp_domain_id(&self) -> Result<Domain> {
+        lookup(self, |config| config.domain_id.map(Domain::new)).or_else(|e| {
+            burn_only(self)
+                .map(|(_, domain_id, _)| Domain::new(*domain_id))
+                .ok_or(e)
+        })
     }
 
     fn token_messenger_address(&self) -> Result<Address> {
-        match self.kind() {
-            ChainKind::Named(n) => n.token_messenger_address(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID | SOLANA_MAINNET_ID => {
-                    Ok(nitrogen_circle_token_messenger_minter_v2_encoder::id().into())
-                }
-                _ => Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                }),
-            },
-        }
+        lookup(self, |config| config.token_messenger)
     }
 
     fn message_transmitter_address(&self) -> Result<Address> {
-        match self.kind() {
-            ChainKind::Named(n) => n.message_transmitter_address(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID | SOLANA_MAINNET_ID => {
-                    Ok(nitrogen_circle_message_transmitter_v2_encoder::id().into())
-                }
-                _ => Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                }),
-            },
-        }
+        lookup(self, |config| config.message_transmitter)
     }
 
     fn is_supported(&self) -> bool {
-        match self.kind() {
-            ChainKind::Named(n) => n.is_supported(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID => true,
-                SOLANA_MAINNET_ID => true,
-                _ => false,
-            },
-        }
+        chain_config(self).is_some_and(|config| config.is_complete())
     }
 
     fn usdc_token_address(&self) -> Result<Address> {
-        match self.kind() {
-            ChainKind::Named(n) => n.usdc_token_address(),
-            ChainKind::Id(id) => match *id {
-                SOLANA_DEVNET_ID => Ok(SOLANA_DEVNET_USDC_TOKEN.into()),
-                SOLANA_MAINNET_ID => Ok(SOLANA_MAINNET_USDC_TOKEN.into()),
-                _ => Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                }),
-            },
-        }
+        lookup(self, |config| config.usdc)
     }
 }
 
 impl CctpChain for NamedChain {
     fn sandbox(&self) -> bool {
-        self.is_testnet()
+        Chain::from(*self).sandbox()
     }
 
     fn confirmation_average_time_seconds(&self) -> Result<u64> {
-        use NamedChain::*;
-
-        match self {
-            Mainnet | Arbitrum | Base | Optimism | Unichain => Ok(19 * 60),
-            Avalanche => Ok(20),
-            Polygon => Ok(8 * 60),
-            // Testnets
-            Sepolia => Ok(60),
-            ArbitrumSepolia | AvalancheFuji | BaseSepolia | OptimismSepolia | PolygonAmoy => Ok(20),
-            _ => Err(Error::ChainNotSupported {
-                chain: self.to_string(),
-            }),
-        }
+        Chain::from(*self).confirmation_average_time_seconds()
     }
 
-    fn cctp_domain_id(&self) -> Result<u32> {
-        use NamedChain::*;
-
-        match self {
-            Arbitrum | ArbitrumSepolia => Ok(ARBITRUM_DOMAIN_ID),
-            Avalanche => Ok(AVALANCHE_DOMAIN_ID),
-            Base | BaseSepolia => Ok(BASE_DOMAIN_ID),
-            Mainnet | Sepolia => Ok(ETHEREUM_DOMAIN_ID),
-            Optimism => Ok(OPTIMISM_DOMAIN_ID),
-            Polygon => Ok(POLYGON_DOMAIN_ID),
-            Unichain => Ok(UNICHAIN_DOMAIN_ID),
-            _ => Err(Error::ChainNotSupported {
-                chain: self.to_string(),
-            }),
-        }
+    fn cctp_domain_id(&self) -> Result<Domain> {
+        Chain::from(*self).cctp_domain_id()
     }
 
     fn token_messenger_address(&self) -> Result<Address> {
-        use NamedChain::*;
-
-        let address = match self {
-            Arbitrum => ARBITRUM_TOKEN_MESSENGER_ADDRESS,
-            ArbitrumSepolia => ARBITRUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS,
-            Avalanche => AVALANCHE_TOKEN_MESSENGER_ADDRESS,
-            Base => BASE_TOKEN_MESSENGER_ADDRESS,
-            BaseSepolia => BASE_SEPOLIA_TOKEN_MESSENGER_ADDRESS,
-            Sepolia => ETHEREUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS,
-            Mainnet => ETHEREUM_TOKEN_MESSENGER_ADDRESS,
-            Optimism => OPTIMISM_TOKEN_MESSENGER_ADDRESS,
-            Polygon => POLYGON_CCTP_TOKEN_MESSENGER,
-            Unichain => UNICHAIN_CCTP_TOKEN_MESSENGER,
-            _ => {
-                return Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                });
-            }
-        };
-        Ok(address.into())
+        Chain::from(*self).token_messenger_address()
     }
 
     fn message_transmitter_address(&self) -> Result<Address> {
-        use NamedChain::*;
-
-        let address_str = match self {
-            Arbitrum => ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS,
-
-            Avalanche => AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS,
-            Base => BASE_MESSAGE_TRANSMITTER_ADDRESS,
-            Mainnet => ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS,
-            Optimism => OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS,
-            Polygon => POLYGON_CCTP_MESSAGE_TRANSMITTER,
-            // Testnets
-            ArbitrumSepolia => ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-            BaseSepolia => BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-            Sepolia => ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
-            Unichain => UNICHAIN_CCTP_MESSAGE_TRANSMITTER,
-            _ => {
-                return Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                });
-            }
-        };
-
-        Ok(address_str.into())
+        Chain::from(*self).message_transmitter_address()
     }
 
     fn is_supported(&self) -> bool {
-        use NamedChain::*;
-
-        matches!(
-            self,
-            Mainnet
-                | Arbitrum
-                | Base
-                | Optimism
-                | Unichain
-                | Avalanche
-                | Polygon
-                | Sepolia
-                | ArbitrumSepolia
-                | AvalancheFuji
-                | BaseSepolia
-                | OptimismSepolia
-                | PolygonAmoy
-        )
+        Chain::from(*self).is_supported()
     }
 
     fn usdc_token_address(&self) -> Result<Address> {
-        use NamedChain::*;
-
-        let address: alloy_primitives::Address = match self {
-            Mainnet => ETHEREUM_USDC_CONTRACT,
-            Arbitrum => ARBITRUM_USDC_CONTRACT,
-            Avalanche => AVALANCHE_USDC_CONTRACT,
-            Base => BASE_USDC_CONTRACT,
-            Optimism => OPTIMISM_USDC_CONTRACT,
-            Polygon => POLYGON_USDC_CONTRACT,
-            // Testnets
-            ArbitrumSepolia => ARBITRUM_SEPOLIA_USDC_CONTRACT,
-            Sepolia => ETHEREUM_SEPOLIA_USDC_CONTRACT,
-            BaseSepolia => BASE_SEPOLIA_USDC_CONTRACT,
-            OptimismSepolia => OPTIMISM_SEPOLIA_USDC_CONTRACT,
-            Unichain => UNICHAIN_USDC_CONTRACT,
-            _ => {
-                return Err(Error::ChainNotSupported {
-                    chain: self.to_string(),
-                });
-            }
-        };
-
-        Ok(address.into())
+        Chain::from(*self).usdc_token_address()
     }
 }
 
@@ -284,7 +137,40 @@ impl CctpChain for NamedChain {
 mod tests {
     use {
         super::*,
-        crate::{SOLANA_DEVNET, SOLANA_MAINNET},
+        crate::{
+            ARBITRUM_DOMAIN_ID,
+            ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS,
+            ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+            AVALANCHE_DOMAIN_ID,
+            AVALANCHE_FUJI_MESSAGE_TRANSMITTER_ADDRESS,
+            AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS,
+            BASE_DOMAIN_ID,
+            BASE_MESSAGE_TRANSMITTER_ADDRESS,
+            BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+            ETHEREUM_DOMAIN_ID,
+            ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS,
+            ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+            OPTIMISM_DOMAIN_ID,
+            OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS,
+            OPTIMISM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+            POLYGON_AMOY_MESSAGE_TRANSMITTER_ADDRESS,
+            POLYGON_CCTP_MESSAGE_TRANSMITTER,
+            POLYGON_DOMAIN_ID,
+            SOLANA_DEVNET,
+            SOLANA_DEVNET_USDC_TOKEN,
+            SOLANA_MAINNET,
+            SOLANA_MAINNET_USDC_TOKEN,
+            UNICHAIN_CCTP_MESSAGE_TRANSMITTER,
+            UNICHAIN_DOMAIN_ID,
+            chain_id::*,
+            domain_id::*,
+            message_transmitter::{
+                MESSAGE_TRANSMITTER_ADDRESS,
+                MESSAGE_TRANSMITTER_ADDRESS_TESTNET,
+            },
+            registry::ChainRegistry,
+            token_messenger::*,
+        },
         alloy_chains::NamedChain,
         rstest::rstest,
     };
@@ -303,9 +189,25 @@ mod tests {
     #[case(NamedChain::BaseSepolia, true)]
     #[case(NamedChain::OptimismSepolia, true)]
     #[case(NamedChain::PolygonAmoy, true)]
+    #[case(Chain::from_id(LINEA_ID), true)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), true)]
+    #[case(Chain::from_id(CODEX_ID), true)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), true)]
+    #[case(Chain::from_id(SONIC_ID), true)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), true)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), true)]
+    #[case(Chain::from_id(WORLD_CHAIN_SEPOLIA_ID), true)]
+    #[case(Chain::from_id(SEI_ID), true)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), true)]
+    #[case(Chain::from_id(HYPEREVM_ID), true)]
+    #[case(Chain::from_id(HYPEREVM_TESTNET_ID), true)]
+    #[case(Chain::from_id(INK_ID), true)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), true)]
+    #[case(Chain::from_id(PLUME_ID), true)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), true)]
     #[case(NamedChain::BinanceSmartChain, false)]
     #[case(NamedChain::Fantom, false)]
-    fn test_is_supported(#[case] chain: NamedChain, #[case] expected: bool) {
+    fn test_is_supported(#[case] chain: impl CctpChain, #[case] expected: bool) {
         assert_eq!(chain.is_supported(), expected);
     }
 
@@ -323,8 +225,24 @@ mod tests {
     #[case(NamedChain::BaseSepolia, 20)]
     #[case(NamedChain::OptimismSepolia, 20)]
     #[case(NamedChain::PolygonAmoy, 20)]
+    #[case(Chain::from_id(LINEA_ID), 8 * 60 * 60)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), 20)]
+    #[case(Chain::from_id(CODEX_ID), 19 * 60)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), 20)]
+    #[case(Chain::from_id(SONIC_ID), 8)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), 8)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), 19 * 60)]
+    #[case(Chain::from_id(WORLD_CHAIN_SEPOLIA_ID), 20)]
+    #[case(Chain::from_id(SEI_ID), 5)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), 5)]
+    #[case(Chain::from_id(HYPEREVM_ID), 5)]
+    #[case(Chain::from_id(HYPEREVM_TESTNET_ID), 5)]
+    #[case(Chain::from_id(INK_ID), 19 * 60)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), 20)]
+    #[case(Chain::from_id(PLUME_ID), 19 * 60)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), 20)]
     fn test_confirmation_average_time_seconds_supported_chains(
-        #[case] chain: NamedChain,
+        #[case] chain: impl CctpChain,
         #[case] expected: u64,
     ) {
         assert_eq!(chain.confirmation_average_time_seconds().unwrap(), expected);
@@ -354,14 +272,33 @@ mod tests {
     #[case(NamedChain::Arbitrum, ARBITRUM_DOMAIN_ID)]
     #[case(NamedChain::ArbitrumSepolia, ARBITRUM_DOMAIN_ID)]
     #[case(NamedChain::Avalanche, AVALANCHE_DOMAIN_ID)]
+    #[case(NamedChain::AvalancheFuji, AVALANCHE_DOMAIN_ID)]
     #[case(NamedChain::Base, BASE_DOMAIN_ID)]
     #[case(NamedChain::BaseSepolia, BASE_DOMAIN_ID)]
     #[case(NamedChain::Mainnet, ETHEREUM_DOMAIN_ID)]
     #[case(NamedChain::Sepolia, ETHEREUM_DOMAIN_ID)]
     #[case(NamedChain::Optimism, OPTIMISM_DOMAIN_ID)]
+    #[case(NamedChain::OptimismSepolia, OPTIMISM_DOMAIN_ID)]
     #[case(NamedChain::Polygon, POLYGON_DOMAIN_ID)]
+    #[case(NamedChain::PolygonAmoy, POLYGON_DOMAIN_ID)]
     #[case(NamedChain::Unichain, UNICHAIN_DOMAIN_ID)]
-    fn test_cctp_domain_id_supported_chains(#[case] chain: NamedChain, #[case] expected: u32) {
+    #[case(Chain::from_id(LINEA_ID), LINEA_DOMAIN_ID)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), LINEA_DOMAIN_ID)]
+    #[case(Chain::from_id(CODEX_ID), CODEX_DOMAIN_ID)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), CODEX_DOMAIN_ID)]
+    #[case(Chain::from_id(SONIC_ID), SONIC_DOMAIN_ID)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), SONIC_DOMAIN_ID)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), WORLD_CHAIN_DOMAIN_ID)]
+    #[case(Chain::from_id(WORLD_CHAIN_SEPOLIA_ID), WORLD_CHAIN_DOMAIN_ID)]
+    #[case(Chain::from_id(SEI_ID), SEI_DOMAIN_ID)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), SEI_DOMAIN_ID)]
+    #[case(Chain::from_id(HYPEREVM_ID), HYPEREVM_DOMAIN_ID)]
+    #[case(Chain::from_id(HYPEREVM_TESTNET_ID), HYPEREVM_DOMAIN_ID)]
+    #[case(Chain::from_id(INK_ID), INK_DOMAIN_ID)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), INK_DOMAIN_ID)]
+    #[case(Chain::from_id(PLUME_ID), PLUME_DOMAIN_ID)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), PLUME_DOMAIN_ID)]
+    fn test_cctp_domain_id_supported_chains(#[case] chain: impl CctpChain, #[case] expected: u32) {
         assert_eq!(chain.cctp_domain_id().unwrap(), expected);
     }
 
@@ -379,15 +316,37 @@ mod tests {
     #[case(NamedChain::Arbitrum, ARBITRUM_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::ArbitrumSepolia, ARBITRUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Avalanche, AVALANCHE_TOKEN_MESSENGER_ADDRESS)]
+    #[case(NamedChain::AvalancheFuji, AVALANCHE_FUJI_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Base, BASE_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::BaseSepolia, BASE_SEPOLIA_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Sepolia, ETHEREUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Mainnet, ETHEREUM_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Optimism, OPTIMISM_TOKEN_MESSENGER_ADDRESS)]
+    #[case(NamedChain::OptimismSepolia, OPTIMISM_SEPOLIA_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Polygon, POLYGON_CCTP_TOKEN_MESSENGER)]
+    #[case(NamedChain::PolygonAmoy, POLYGON_AMOY_TOKEN_MESSENGER_ADDRESS)]
     #[case(NamedChain::Unichain, UNICHAIN_CCTP_TOKEN_MESSENGER)]
+    #[case(Chain::from_id(LINEA_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(CODEX_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(SONIC_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(
+        Chain::from_id(WORLD_CHAIN_SEPOLIA_ID),
+        TOKEN_MESSENGER_CONTRACT_TESTNET
+    )]
+    #[case(Chain::from_id(SEI_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(HYPEREVM_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(HYPEREVM_TESTNET_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(INK_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
+    #[case(Chain::from_id(PLUME_ID), TOKEN_MESSENGER_CONTRACT)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), TOKEN_MESSENGER_CONTRACT_TESTNET)]
     fn test_token_messenger_address_supported_chains(
-        #[case] chain: NamedChain,
+        #[case] chain: impl CctpChain,
         #[case] expected_addr: alloy_primitives::Address,
     ) -> anyhow::Result<()> {
         let result: alloy_primitives::Address = chain.token_messenger_address()?.try_into()?;
@@ -406,9 +365,30 @@ mod tests {
     #[case(NamedChain::Sepolia, ETHEREUM_SEPOLIA_USDC_CONTRACT)]
     #[case(NamedChain::BaseSepolia, BASE_SEPOLIA_USDC_CONTRACT)]
     #[case(NamedChain::OptimismSepolia, OPTIMISM_SEPOLIA_USDC_CONTRACT)]
+    #[case(NamedChain::AvalancheFuji, AVALANCHE_FUJI_USDC_CONTRACT)]
+    #[case(NamedChain::PolygonAmoy, POLYGON_AMOY_USDC_CONTRACT)]
     #[case(NamedChain::Unichain, UNICHAIN_USDC_CONTRACT)]
+    #[case(Chain::from_id(LINEA_ID), LINEA_USDC_CONTRACT)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), LINEA_SEPOLIA_USDC_CONTRACT)]
+    #[case(Chain::from_id(CODEX_ID), CODEX_USDC_CONTRACT)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), CODEX_TESTNET_USDC_CONTRACT)]
+    #[case(Chain::from_id(SONIC_ID), SONIC_USDC_CONTRACT)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), SONIC_TESTNET_USDC_CONTRACT)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), WORLD_CHAIN_USDC_CONTRACT)]
+    #[case(
+        Chain::from_id(WORLD_CHAIN_SEPOLIA_ID),
+        WORLD_CHAIN_SEPOLIA_USDC_CONTRACT
+    )]
+    #[case(Chain::from_id(SEI_ID), SEI_USDC_CONTRACT)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), SEI_TESTNET_USDC_CONTRACT)]
+    #[case(Chain::from_id(HYPEREVM_ID), HYPEREVM_USDC_CONTRACT)]
+    #[case(Chain::from_id(HYPEREVM_TESTNET_ID), HYPEREVM_TESTNET_USDC_CONTRACT)]
+    #[case(Chain::from_id(INK_ID), INK_USDC_CONTRACT)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), INK_SEPOLIA_USDC_CONTRACT)]
+    #[case(Chain::from_id(PLUME_ID), PLUME_USDC_CONTRACT)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), PLUME_TESTNET_USDC_CONTRACT)]
     fn test_evm_usdc_address(
-        #[case] chain: NamedChain,
+        #[case] chain: impl CctpChain,
         #[case] expected_addr: alloy_primitives::Address,
     ) -> anyhow::Result<()> {
         let result: alloy_primitives::Address = chain.usdc_token_address()?.try_into()?;
@@ -475,9 +455,37 @@ mod tests {
     )]
     #[case(NamedChain::BaseSepolia, BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS)]
     #[case(NamedChain::Sepolia, ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(NamedChain::AvalancheFuji, AVALANCHE_FUJI_MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(
+        NamedChain::OptimismSepolia,
+        OPTIMISM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS
+    )]
+    #[case(NamedChain::PolygonAmoy, POLYGON_AMOY_MESSAGE_TRANSMITTER_ADDRESS)]
     #[case(NamedChain::Unichain, UNICHAIN_CCTP_MESSAGE_TRANSMITTER)]
+    #[case(Chain::from_id(LINEA_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(LINEA_SEPOLIA_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
+    #[case(Chain::from_id(CODEX_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(CODEX_TESTNET_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
+    #[case(Chain::from_id(SONIC_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(SONIC_TESTNET_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
+    #[case(Chain::from_id(WORLD_CHAIN_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(
+        Chain::from_id(WORLD_CHAIN_SEPOLIA_ID),
+        MESSAGE_TRANSMITTER_ADDRESS_TESTNET
+    )]
+    #[case(Chain::from_id(SEI_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(SEI_TESTNET_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
+    #[case(Chain::from_id(HYPEREVM_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(
+        Chain::from_id(HYPEREVM_TESTNET_ID),
+        MESSAGE_TRANSMITTER_ADDRESS_TESTNET
+    )]
+    #[case(Chain::from_id(INK_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(INK_SEPOLIA_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
+    #[case(Chain::from_id(PLUME_ID), MESSAGE_TRANSMITTER_ADDRESS)]
+    #[case(Chain::from_id(PLUME_TESTNET_ID), MESSAGE_TRANSMITTER_ADDRESS_TESTNET)]
     fn test_message_transmitter_address_supported_chains(
-        #[case] chain: NamedChain,
+        #[case] chain: impl CctpChain,
         #[case] expected_addr: alloy_primitives::Address,
     ) -> anyhow::Result<()> {
         let result: alloy_primitives::Address =
@@ -509,7 +517,10 @@ mod tests {
             NamedChain::Polygon,
             NamedChain::Sepolia,
             NamedChain::ArbitrumSepolia,
+            NamedChain::AvalancheFuji,
             NamedChain::BaseSepolia,
+            NamedChain::OptimismSepolia,
+            NamedChain::PolygonAmoy,
         ] {
             assert!(
                 chain.token_messenger_address().is_ok(),
@@ -521,4 +532,39 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_every_supported_chain_is_complete() {
+        for config in ChainRegistry::builtin().chains() {
+            let chain = config.chain();
+            assert!(
+                chain.is_supported(),
+                "{chain} has an incomplete registry entry"
+            );
+            assert!(chain.confirmation_average_time_seconds().is_ok(), "{chain}");
+            assert!(chain.cctp_domain_id().is_ok(), "{chain}");
+            assert!(chain.token_messenger_address().is_ok(), "{chain}");
+            assert!(chain.message_transmitter_address().is_ok(), "{chain}");
+            assert!(chain.usdc_token_address().is_ok(), "{chain}");
+            assert_eq!(
+                chain.sandbox(),
+                config.sandbox.unwrap_or_default(),
+                "{chain}"
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(NOBLE, Domain::NOBLE, false)]
+    #[case(NOBLE_TESTNET, Domain::NOBLE, true)]
+    #[case(SUI, Domain::SUI, false)]
+    #[case(SUI_TESTNET, Domain::SUI, true)]
+    #[case(APTOS, Domain::APTOS, false)]
+    #[case(APTOS_TESTNET, Domain::APTOS, true)]
+    fn test_burn_only_chains(#[case] chain: Chain, #[case] domain: Domain, #[case] sandbox: bool) {
+        assert!(!chain.is_supported());
+        assert_eq!(chain.cctp_domain_id().ok(), Some(domain));
+        assert_eq!(chain.sandbox(), sandbox);
+        assert!(chain.token_messenger_address().is_err());
+    }
 }
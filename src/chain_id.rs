@@ -0,0 +1,49 @@
+//! Chain ids of CCTP v2 EVM chains, use them with
+//! [`alloy_chains::Chain::from_id`] as not every chain has a
+//! [`alloy_chains::NamedChain`] variant
+//!
+//! <https://developers.circle.com/cctp/evm-smart-contracts>
+use alloy_chains::Chain;
+
+pub const LINEA_ID: u64 = 59144;
+pub const LINEA_SEPOLIA_ID: u64 = 59141;
+
+pub const CODEX_ID: u64 = 81224;
+pub const CODEX_TESTNET_ID: u64 = 812242;
+
+pub const SONIC_ID: u64 = 146;
+pub const SONIC_TESTNET_ID: u64 = 57054;
+
+pub const WORLD_CHAIN_ID: u64 = 480;
+pub const WORLD_CHAIN_SEPOLIA_ID: u64 = 4801;
+
+pub const SEI_ID: u64 = 1329;
+pub const SEI_TESTNET_ID: u64 = 1328;
+
+pub const HYPEREVM_ID: u64 = 999;
+pub const HYPEREVM_TESTNET_ID: u64 = 998;
+
+pub const INK_ID: u64 = 57073;
+pub const INK_SEPOLIA_ID: u64 = 763373;
+
+pub const PLUME_ID: u64 = 98866;
+pub const PLUME_TESTNET_ID: u64 = 98867;
+
+/// Chain ids of the non EVM domains CCTP can burn to, FNV hashes of their
+/// CAIP-2 ids like [`SOLANA_MAINNET`](crate::SOLANA_MAINNET). Receiving on
+/// them is left to the chain's own tooling
+pub const NOBLE_ID: u64 = chain_id_from_reown!("cosmos:noble-1");
+pub const NOBLE_TESTNET_ID: u64 = chain_id_from_reown!("cosmos:grand-1");
+
+pub const SUI_ID: u64 = chain_id_from_reown!("sui:mainnet");
+pub const SUI_TESTNET_ID: u64 = chain_id_from_reown!("sui:testnet");
+
+pub const APTOS_ID: u64 = chain_id_from_reown!("aptos:1");
+pub const APTOS_TESTNET_ID: u64 = chain_id_from_reown!("aptos:2");
+
+pub const NOBLE: Chain = Chain::from_id_unchecked(NOBLE_ID);
+pub const NOBLE_TESTNET: Chain = Chain::from_id_unchecked(NOBLE_TESTNET_ID);
+pub const SUI: Chain = Chain::from_id_unchecked(SUI_ID);
+pub const SUI_TESTNET: Chain = Chain::from_id_unchecked(SUI_TESTNET_ID);
+pub const APTOS: Chain = Chain::from_id_unchecked(APTOS_ID);
+pub const APTOS_TESTNET: Chain = Chain::from_id_unchecked(APTOS_TESTNET_ID);
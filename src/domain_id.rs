@@ -1,4 +1,10 @@
 //! <https://developers.circle.com/stablecoins/evm-smart-contracts>
+use {
+    crate::{AddressKind, CctpChain, ChainRegistry, Error},
+    alloy_chains::Chain,
+    serde::{Deserialize, Serialize},
+    std::fmt::{Display, Formatter},
+};
 
 pub const ARBITRUM_DOMAIN_ID: u32 = 3;
 
@@ -15,3 +21,234 @@ pub const POLYGON_DOMAIN_ID: u32 = 7;
 pub const UNICHAIN_DOMAIN_ID: u32 = 10;
 
 pub const SOLANA_DOMAIN_ID: u32 = 5;
+
+pub const LINEA_DOMAIN_ID: u32 = 11;
+
+pub const CODEX_DOMAIN_ID: u32 = 12;
+
+pub const SONIC_DOMAIN_ID: u32 = 13;
+
+pub const WORLD_CHAIN_DOMAIN_ID: u32 = 14;
+
+pub const SEI_DOMAIN_ID: u32 = 16;
+
+pub const HYPEREVM_DOMAIN_ID: u32 = 19;
+
+pub const INK_DOMAIN_ID: u32 = 21;
+
+pub const PLUME_DOMAIN_ID: u32 = 22;
+
+pub const NOBLE_DOMAIN_ID: u32 = 4;
+
+pub const SUI_DOMAIN_ID: u32 = 8;
+
+pub const APTOS_DOMAIN_ID: u32 = 9;
+
+/// Names of the domains known to the crate
+const DOMAIN_NAMES: &[(u32, &str)] = &[
+    (ETHEREUM_DOMAIN_ID, "Ethereum"),
+    (AVALANCHE_DOMAIN_ID, "Avalanche"),
+    (OPTIMISM_DOMAIN_ID, "Optimism"),
+    (ARBITRUM_DOMAIN_ID, "Arbitrum"),
+    (NOBLE_DOMAIN_ID, "Noble"),
+    (SOLANA_DOMAIN_ID, "Solana"),
+    (BASE_DOMAIN_ID, "Base"),
+    (POLYGON_DOMAIN_ID, "Polygon"),
+    (SUI_DOMAIN_ID, "Sui"),
+    (APTOS_DOMAIN_ID, "Aptos"),
+    (UNICHAIN_DOMAIN_ID, "Unichain"),
+    (LINEA_DOMAIN_ID, "Linea"),
+    (CODEX_DOMAIN_ID, "Codex"),
+    (SONIC_DOMAIN_ID, "Sonic"),
+    (WORLD_CHAIN_DOMAIN_ID, "World Chain"),
+    (SEI_DOMAIN_ID, "Sei"),
+    (HYPEREVM_DOMAIN_ID, "HyperEVM"),
+    (INK_DOMAIN_ID, "Ink"),
+    (PLUME_DOMAIN_ID, "Plume"),
+];
+
+/// A CCTP domain id.
+///
+/// Converting from `u32` succeeds for the domains known to the crate and the
+/// domains of the [`ChainRegistry`], so ids read from messages or the Iris API
+/// can be mapped back to chains with [`Domain::chains`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct Domain(u32);
+
+impl Domain {
+    pub const APTOS: Domain = Domain(APTOS_DOMAIN_ID);
+    pub const ARBITRUM: Domain = Domain(ARBITRUM_DOMAIN_ID);
+    pub const AVALANCHE: Domain = Domain(AVALANCHE_DOMAIN_ID);
+    pub const BASE: Domain = Domain(BASE_DOMAIN_ID);
+    pub const CODEX: Domain = Domain(CODEX_DOMAIN_ID);
+    pub const ETHEREUM: Domain = Domain(ETHEREUM_DOMAIN_ID);
+    pub const HYPEREVM: Domain = Domain(HYPEREVM_DOMAIN_ID);
+    pub const INK: Domain = Domain(INK_DOMAIN_ID);
+    pub const LINEA: Domain = Domain(LINEA_DOMAIN_ID);
+    pub const NOBLE: Domain = Domain(NOBLE_DOMAIN_ID);
+    pub const OPTIMISM: Domain = Domain(OPTIMISM_DOMAIN_ID);
+    pub const PLUME: Domain = Domain(PLUME_DOMAIN_ID);
+    pub const POLYGON: Domain = Domain(POLYGON_DOMAIN_ID);
+    pub const SEI: Domain = Domain(SEI_DOMAIN_ID);
+    pub const SOLANA: Domain = Domain(SOLANA_DOMAIN_ID);
+    pub const SONIC: Domain = Domain(SONIC_DOMAIN_ID);
+    pub const SUI: Domain = Domain(SUI_DOMAIN_ID);
+    pub const UNICHAIN: Domain = Domain(UNICHAIN_DOMAIN_ID);
+    pub const WORLD_CHAIN: Domain = Domain(WORLD_CHAIN_DOMAIN_ID);
+
+    /// A domain read from the [`ChainRegistry`], which is known by definition
+    pub(crate) const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub const fn id(self) -> u32 {
+        self.0
+    }
+
+    /// The name of the domain, `None` for domains only known from the
+    /// [`ChainRegistry`]
+    pub fn name(self) -> Option<&'static str> {
+        DOMAIN_NAMES
+            .iter()
+            .find(|(id, _)| *id == self.0)
+            .map(|(_, name)| *name)
+    }
+
+    /// The kind of the accounts of this domain, which decides how a mint
+    /// recipient is encoded. Domains only known from the [`ChainRegistry`]
+    /// take the kind of their `TokenMessenger`, and fail with
+    /// [`Error::UnknownDomain`] without one
+    pub fn address_kind(self) -> Result<AddressKind, Error> {
+        match self.0 {
+            SOLANA_DOMAIN_ID => Ok(AddressKind::Solana),
+            NOBLE_DOMAIN_ID => Ok(AddressKind::Noble),
+            SUI_DOMAIN_ID | APTOS_DOMAIN_ID => Ok(AddressKind::Move),
+            _ if self.name().is_some() => Ok(AddressKind::Evm),
+            id => ChainRegistry::global()
+                .chains()
+                .filter(|config| config.domain_id == Some(id))
+                .find_map(|config| config.token_messenger)
+                .map(|token_messenger| token_messenger.kind())
+                .ok_or(Error::UnknownDomain(id)),
+        }
+    }
+
+    /// The supported chains of this domain in the global [`ChainRegistry`],
+    /// testnets when `sandbox` is set
+    pub fn chains(self, sandbox: bool) -> Vec<Chain> {
+        ChainRegistry::global()
+            .chains()
+            .filter(|config| config.domain_id == Some(self.0))
+            .map(|config| config.chain())
+            .filter(|chain| chain.is_supported() && chain.sandbox() == sandbox)
+            .collect()
+    }
+}
+
+impl TryFrom<u32> for Domain {
+    type Error = Error;
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        let known = DOMAIN_NAMES.iter().any(|(known, _)| *known == id)
+            || ChainRegistry::global()
+                .chains()
+                .any(|config| config.domain_id == Some(id));
+        if known {
+            Ok(Self(id))
+        } else {
+            Err(Error::UnknownDomain(id))
+        }
+    }
+}
+
+impl From<Domain> for u32 {
+    fn from(domain: Domain) -> Self {
+        domain.0
+    }
+}
+
+impl PartialEq<u32> for Domain {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for Domain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{name} ({})", self.0),
+            None => write!(f, "domain {}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{LINEA_ID, LINEA_SEPOLIA_ID, SOLANA_DEVNET, SOLANA_MAINNET},
+        alloy_chains::NamedChain,
+        rstest::rstest,
+    };
+
+    #[rstest]
+    #[case(6, "Base (6)")]
+    #[case(5, "Solana (5)")]
+    #[case(14, "World Chain (14)")]
+    fn test_try_from(#[case] id: u32, #[case] display: &str) -> anyhow::Result<()> {
+        let domain = Domain::try_from(id)?;
+        assert_eq!(domain, id);
+        assert_eq!(domain.to_string(), display);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_domain() {
+        assert!(matches!(
+            Domain::try_from(9999),
+            Err(Error::UnknownDomain(9999))
+        ));
+        assert!(serde_json::from_str::<Domain>("9999").is_err());
+    }
+
+    #[rstest]
+    #[case(Domain::BASE, AddressKind::Evm)]
+    #[case(Domain::SOLANA, AddressKind::Solana)]
+    #[case(Domain::NOBLE, AddressKind::Noble)]
+    #[case(Domain::SUI, AddressKind::Move)]
+    #[case(Domain::APTOS, AddressKind::Move)]
+    fn test_address_kind(#[case] domain: Domain, #[case] kind: AddressKind) -> anyhow::Result<()> {
+        assert_eq!(domain.address_kind()?, kind);
+        Ok(())
+    }
+
+    #[test]
+    fn test_address_kind_unknown_domain() {
+        assert!(matches!(
+            Domain::new(9999).address_kind(),
+            Err(Error::UnknownDomain(9999))
+        ));
+    }
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        assert_eq!(serde_json::to_string(&Domain::BASE)?, "6");
+        assert_eq!(serde_json::from_str::<Domain>("6")?, Domain::BASE);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Domain::ETHEREUM, false, vec![NamedChain::Mainnet.into()])]
+    #[case(Domain::ETHEREUM, true, vec![NamedChain::Sepolia.into()])]
+    #[case(Domain::SOLANA, false, vec![SOLANA_MAINNET])]
+    #[case(Domain::SOLANA, true, vec![SOLANA_DEVNET])]
+    #[case(Domain::LINEA, false, vec![Chain::from_id(LINEA_ID)])]
+    #[case(Domain::LINEA, true, vec![Chain::from_id(LINEA_SEPOLIA_ID)])]
+    #[case(Domain::NOBLE, false, vec![])]
+    fn test_chains(#[case] domain: Domain, #[case] sandbox: bool, #[case] expected: Vec<Chain>) {
+        let ids: Vec<u64> = domain.chains(sandbox).iter().map(Chain::id).collect();
+        let expected: Vec<u64> = expected.iter().map(Chain::id).collect();
+        assert_eq!(ids, expected);
+    }
+}
@@ -1,13 +1,73 @@
 use {
+    crate::{CctpRevert, Operation, TransferTx},
+    alloy_json_rpc::RpcError,
     alloy_primitives::{hex::FromHexError, ruint::aliases::U256},
+    solana_rpc_client_api::client_error::ErrorKind as SolanaErrorKind,
+    std::fmt::{Display, Formatter},
     thiserror::Error,
 };
 
+/// The step of a transfer an [`Error`] happened in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Approve,
+    Burn,
+    /// Reading the burn's message and polling Iris
+    Attest,
+    Receive,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Approve => write!(f, "approve"),
+            Self::Burn => write!(f, "burn"),
+            Self::Attest => write!(f, "attest"),
+            Self::Receive => write!(f, "receive"),
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
+    /// `source` failed in `stage`, after the burn `burn` for the attest and
+    /// receive stages
+    #[error(
+        "{stage} failed{}: {source}",
+        .burn.map(|burn| format!(" after burn {burn}")).unwrap_or_default()
+    )]
+    Stage {
+        stage: Stage,
+        burn: Option<TransferTx>,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// `source` happened after `tx` was broadcast, which may still be mined
+    #[error("{source}, check the status of {operation:?} transaction {tx} before retrying")]
+    Broadcast {
+        operation: Operation,
+        tx: String,
+        #[source]
+        source: Box<Error>,
+    },
+
     #[error("Chain not supported: {chain}")]
     ChainNotSupported { chain: String },
 
+    #[error("No CCTP route from {from} to {to}: {reason}")]
+    InvalidRoute {
+        from: String,
+        to: String,
+        reason: String,
+    },
+
+    #[error("Unknown CCTP domain: {0}")]
+    UnknownDomain(u32),
+
+    #[error("Invalid CAIP identifier {0}")]
+    InvalidCaip(String),
+
     #[error("Invalid address: {address}")]
     InvalidAddress {
         address: String,
@@ -20,21 +80,61 @@ pub enum Error {
     #[error("Provider error: {0}")]
     Provider(String),
 
-    #[error("Contract call failed: {0}")]
+    #[error("Pending transaction failed: {0}")]
     PendingError(#[from] alloy_provider::PendingTransactionError),
 
-    #[error("Contract call failed: {0}")]
+    #[error("Contract error: {0}")]
     ContractError(#[from] alloy_contract::Error),
 
     #[error("Contract call failed: {0}")]
     ContractCall(String),
 
+    #[error("Reverted: {0}")]
+    Revert(#[from] CctpRevert),
+
+    #[error("Simulation failed: {reason}")]
+    SimulationFailed { reason: String, logs: Vec<String> },
+
+    /// A [`SimulationMode::DryRun`](crate::SimulationMode::DryRun) reached a
+    /// call that needs a broadcast transaction, see
+    /// [`Sent::broadcast`](crate::Sent::broadcast)
+    #[error("Dry run broadcast nothing after {} simulations", .0.len())]
+    DryRun(Vec<crate::Simulation>),
+
     #[error("Attestation failed: {reason}")]
     AttestationFailed { reason: String },
 
     #[error("Transaction failed: {reason}")]
     TransactionFailed { reason: String },
 
+    #[error("Transaction {tx} with nonce {nonce} not confirmed after replacements")]
+    TransactionStuck {
+        nonce: u64,
+        tx: alloy_primitives::TxHash,
+    },
+
+    #[error("Transaction {tx} not {policy} after {timeout:?}")]
+    ConfirmationTimeout {
+        tx: String,
+        policy: crate::ConfirmationPolicy,
+        timeout: std::time::Duration,
+    },
+
+    #[error("Bundler error {code}: {message}")]
+    Bundler { code: i64, message: String },
+
+    #[error("User operation {user_op_hash} failed: {reason}")]
+    UserOperationFailed {
+        user_op_hash: alloy_primitives::B256,
+        reason: String,
+    },
+
+    #[error("User operation {user_op_hash} not included after {timeout:?}")]
+    UserOperationTimeout {
+        user_op_hash: alloy_primitives::B256,
+        timeout: std::time::Duration,
+    },
+
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
@@ -59,6 +159,9 @@ pub enum Error {
     #[error("Address conversion error: {0}")]
     AddrError(String),
 
+    #[error(transparent)]
+    ParseAddress(#[from] crate::ParseAddressError),
+
     #[error("max fee {0} > amount {1}")]
     SolanaInvalidFee(u64, u64),
 
@@ -71,8 +174,285 @@ pub enum Error {
     #[error("failed to get solana fee recipient account: {0}")]
     SolanaFeeRecipientError(String),
 
+    #[error("Solana signing error: {0}")]
+    SolanaSigner(#[from] solana_signer::SignerError),
+
+    #[error("Solana RPC error: {0}")]
+    SolanaRpc(#[from] solana_rpc_client_api::client_error::Error),
+
+    #[error("Solana transaction encoding error: {0}")]
+    SolanaEncoding(#[from] bincode::Error),
+
+    #[error("Invalid solana token account {account}: {reason}")]
+    SolanaTokenAccount {
+        account: solana_pubkey::Pubkey,
+        reason: String,
+    },
+
     #[error("Insufficient balance have {0} need {1}")]
     InsufficientBalance(U256, U256),
+
+    #[error("Insufficient allowance have {0} need {1}")]
+    InsufficientAllowance(U256, U256),
+}
+
+impl Error {
+    /// Marks `self` as a failure of `stage`, after `burn` when it was sent.
+    /// An error that already has a stage keeps it and only gains `burn`
+    pub fn at_stage(self, stage: Stage, burn: Option<TransferTx>) -> Self {
+        match self {
+            Self::Stage {
+                stage,
+                burn: inner,
+                source,
+            } => Self::Stage {
+                stage,
+                burn: inner.or(burn),
+                source,
+            },
+            source => Self::Stage {
+                stage,
+                burn,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// The step of the transfer that failed, when known
+    pub fn stage(&self) -> Option<Stage> {
+        match self {
+            Self::Stage { stage, .. } => Some(*stage),
+            Self::InsufficientAllowance(..) => Some(Stage::Approve),
+            Self::InsufficientBalance(..) | Self::SolanaInvalidFee(..) => Some(Stage::Burn),
+            Self::AttestationFailed { .. } | Self::AttestationTimeout | Self::EmptyAttestation => {
+                Some(Stage::Attest)
+            }
+            _ => None,
+        }
+    }
+
+    /// Marks `self` as happening after `tx` of `operation` was broadcast. The
+    /// transaction of a burn becomes the [`Error::burn`]
+    pub(crate) fn after_broadcast(self, operation: Operation, tx: impl Into<TransferTx>) -> Self {
+        let tx = tx.into();
+        let error = Self::Broadcast {
+            operation,
+            tx: tx.to_string(),
+            source: Box::new(self),
+        };
+        match operation {
+            Operation::Burn => error.at_stage(Stage::Burn, Some(tx)),
+            _ => error,
+        }
+    }
+
+    /// The operation and transaction broadcast before the failure, its
+    /// status must be checked before retrying
+    pub fn broadcast(&self) -> Option<(Operation, &str)> {
+        match self {
+            Self::Stage { source, .. } => source.broadcast(),
+            Self::Broadcast { operation, tx, .. } => Some((*operation, tx)),
+            _ => None,
+        }
+    }
+
+    /// The burn sent before the failure, needed to attest and receive it later
+    pub fn burn(&self) -> Option<TransferTx> {
+        match self {
+            Self::Stage { burn, .. } => *burn,
+            _ => None,
+        }
+    }
+
+    /// The error without its [`Error::Stage`] and [`Error::Broadcast`] context
+    pub fn root(&self) -> &Error {
+        match self {
+            Self::Stage { source, .. } | Self::Broadcast { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// The simulations of a dry run that stopped before a broadcast
+    pub fn dry_run(&self) -> Option<&[crate::Simulation]> {
+        match self.root() {
+            Self::DryRun(simulations) => Some(simulations),
+            _ => None,
+        }
+    }
+
+    /// The CCTP revert carried by a contract call or Solana transaction error
+    pub fn revert(&self) -> Option<CctpRevert> {
+        match self.root() {
+            Self::Revert(revert) => Some(revert.clone()),
+            Self::Rpc(e)
+            | Self::PendingError(alloy_provider::PendingTransactionError::TransportError(e))
+            | Self::ContractError(alloy_contract::Error::TransportError(e)) => {
+                CctpRevert::from_rpc_error(e)
+            }
+            Self::SolanaRpc(e) => CctpRevert::from_solana_client_error(e),
+            Self::SolanaSendError(e) => CctpRevert::from_solana_error_source(e),
+            _ => None,
+        }
+    }
+
+    /// Replaces an error carrying a CCTP revert by [`Error::Revert`], keeping
+    /// its [`Stage`] and [`Error::Broadcast`] context
+    pub fn decode_revert(self) -> Self {
+        match self {
+            Self::Stage {
+                stage,
+                burn,
+                source,
+            } => Self::Stage {
+                stage,
+                burn,
+                source: Box::new(source.decode_revert()),
+            },
+            Self::Broadcast {
+                operation,
+                tx,
+                source,
+            } => Self::Broadcast {
+                operation,
+                tx,
+                source: Box::new(source.decode_revert()),
+            },
+            error => match error.revert() {
+                Some(revert) => Self::Revert(revert),
+                None => error,
+            },
+        }
+    }
+
+    /// Whether the failed step may succeed when retried, e.g. after a
+    /// transport failure or an attestation timeout. Reverts, invalid input and
+    /// insufficient funds are fatal, and failures after a broadcast need a
+    /// status check, see [`Error::broadcast`]
+    pub fn is_retryable(&self) -> bool {
+        if self.broadcast().is_some() {
+            return false;
+        }
+        match self.root() {
+            Self::Network(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| {
+                        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    })
+            }
+            Self::Rpc(e) => is_retryable_rpc(e),
+            Self::PendingError(alloy_provider::PendingTransactionError::TransportError(e)) => {
+                is_retryable_rpc(e)
+            }
+            Self::ContractError(alloy_contract::Error::TransportError(e)) => is_retryable_rpc(e),
+            Self::SolanaRpc(e) => matches!(
+                e.kind(),
+                SolanaErrorKind::Io(_) | SolanaErrorKind::Reqwest(_)
+            ),
+            Self::Provider(_)
+            | Self::AttestationTimeout
+            | Self::EmptyAttestation
+            | Self::SolanaClaimableAccountsError(_)
+            | Self::SolanaFeeRecipientError(_) => true,
+            _ => false,
+        }
+    }
+}
+
+fn is_retryable_rpc(e: &RpcError<alloy_transport::TransportErrorKind>) -> bool {
+    match e {
+        RpcError::Transport(kind) => kind.is_retry_err(),
+        RpcError::ErrorResp(payload) => payload.is_retry_err(),
+        RpcError::NullResp => true,
+        _ => false,
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloy_primitives::TxHash, rstest::rstest};
+
+    #[rstest]
+    #[case(Error::AttestationTimeout, true)]
+    #[case(Error::EmptyAttestation, true)]
+    #[case(Error::Provider("connection reset".to_string()), true)]
+    #[case(Error::InsufficientBalance(U256::ZERO, U256::ONE), false)]
+    #[case(Error::ChainNotSupported { chain: "bsc".to_string() }, false)]
+    #[case(Error::ContractCall("reverted".to_string()), false)]
+    #[case(Error::AttestationTimeout.at_stage(Stage::Attest, None), true)]
+    #[case(Error::TransactionFailed { reason: "reverted".to_string() }.at_stage(Stage::Receive, None), false)]
+    #[case(Error::TransactionStuck { nonce: 1, tx: TxHash::ZERO }, false)]
+    #[case(Error::Provider("timeout".to_string()).after_broadcast(Operation::Approve, TxHash::ZERO), false)]
+    fn test_is_retryable(#[case] error: Error, #[case] retryable: bool) {
+        assert_eq!(error.is_retryable(), retryable);
+    }
+
+    #[test]
+    fn test_decode_revert() {
+        let error = Error::Revert(CctpRevert::NonceAlreadyUsed).at_stage(Stage::Receive, None);
+        assert_eq!(error.revert(), Some(CctpRevert::NonceAlreadyUsed));
+        assert!(!error.is_retryable());
+        assert_eq!(Error::AttestationTimeout.revert(), None);
+        assert!(matches!(
+            Error::AttestationTimeout.decode_revert(),
+            Error::AttestationTimeout
+        ));
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let simulation = crate::Simulation {
+            operation: crate::Operation::Burn,
+            cost: crate::SimulatedCost::Gas(100_000),
+        };
+        let error = Error::DryRun(vec![simulation]).at_stage(Stage::Burn, None);
+        assert_eq!(error.dry_run(), Some([simulation].as_slice()));
+        assert!(!error.is_retryable());
+        assert_eq!(Error::AttestationTimeout.dry_run(), None);
+    }
+
+    #[test]
+    fn test_after_broadcast() {
+        let burn = TransferTx::Evm(TxHash::repeat_byte(1));
+        let error = Error::Provider("timeout".to_string())
+            .after_broadcast(Operation::Burn, burn)
+            .at_stage(Stage::Burn, None);
+        assert_eq!(error.stage(), Some(Stage::Burn));
+        assert_eq!(error.burn(), Some(burn));
+        assert_eq!(
+            error.broadcast(),
+            Some((Operation::Burn, burn.to_string().as_str()))
+        );
+        assert!(matches!(error.root(), Error::Provider(_)));
+        assert!(!error.is_retryable());
+
+        let approve = Error::Provider("timeout".to_string())
+            .after_broadcast(Operation::Approve, TxHash::repeat_byte(2));
+        assert_eq!(approve.burn(), None);
+        assert!(approve.broadcast().is_some());
+    }
+
+    #[test]
+    fn test_stage() {
+        assert_eq!(Error::AttestationTimeout.stage(), Some(Stage::Attest));
+        assert_eq!(
+            Error::InsufficientBalance(U256::ZERO, U256::ONE).stage(),
+            Some(Stage::Burn)
+        );
+        assert_eq!(Error::Provider(String::new()).stage(), None);
+
+        let burn = TransferTx::Evm(TxHash::repeat_byte(1));
+        let error = Error::Provider("timeout".to_string())
+            .at_stage(Stage::Receive, None)
+            .at_stage(Stage::Attest, Some(burn));
+        assert_eq!(error.stage(), Some(Stage::Receive));
+        assert_eq!(error.burn(), Some(burn));
+        assert!(matches!(error.root(), Error::Provider(_)));
+        assert_eq!(
+            error.to_string(),
+            format!("receive failed after burn {burn}: Provider error: timeout")
+        );
+    }
+}
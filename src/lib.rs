@@ -17,23 +17,37 @@ macro_rules! chain_id_from_reown {
 mod address;
 mod attestation;
 mod bridge;
+mod caip;
 mod chain;
+mod chain_id;
 mod domain_id;
 mod erc;
 mod error;
 mod message_transmitter;
+mod registry;
+mod revert;
+mod safe;
 mod solana;
 mod token_messenger;
+mod transfer_tx;
+mod user_operation;
 
 pub use {
     address::*,
     attestation::*,
     bridge::*,
+    caip::*,
     chain::*,
+    chain_id::*,
     domain_id::*,
     erc::*,
     error::*,
     message_transmitter::*,
+    registry::*,
+    revert::*,
+    safe::*,
     solana::*,
     token_messenger::*,
+    transfer_tx::*,
+    user_operation::*,
 };
@@ -12,13 +12,17 @@ pub const ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_AD
 pub const ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS: Address =
     MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS;
+pub const AVALANCHE_FUJI_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const BASE_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS;
 pub const BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS;
 pub const ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS: Address =
     MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS;
+pub const OPTIMISM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS: Address =
+    MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const POLYGON_CCTP_MESSAGE_TRANSMITTER: Address = MESSAGE_TRANSMITTER_ADDRESS;
+pub const POLYGON_AMOY_MESSAGE_TRANSMITTER_ADDRESS: Address = MESSAGE_TRANSMITTER_ADDRESS_TESTNET;
 pub const UNICHAIN_CCTP_MESSAGE_TRANSMITTER: Address = MESSAGE_TRANSMITTER_ADDRESS;
 
 sol!(
@@ -0,0 +1,509 @@
+//! Per chain CCTP deployment facts.
+//!
+//! [`CctpChain`](crate::CctpChain) reads everything it knows about a chain from
+//! the global [`ChainRegistry`]. It starts with [`ChainRegistry::builtin`] and
+//! can be overridden at runtime, e.g. to add a newly launched CCTP domain:
+//!
+//! ```toml
+//! [[chains]]
+//! chain_id = 123456
+//! domain_id = 42
+//! token_messenger = "0x28b5a0e9C621a5BadaA536219b3a228C8168cf5d"
+//! message_transmitter = "0x81D40F21F12A8F0E3252Bccb954D722d4c464B64"
+//! usdc = "0x0000000000000000000000000000000000000001"
+//! confirmation_average_time_seconds = 60
+//! ```
+use {
+    crate::{
+        ARBITRUM_DOMAIN_ID,
+        ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS,
+        ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+        AVALANCHE_DOMAIN_ID,
+        AVALANCHE_FUJI_MESSAGE_TRANSMITTER_ADDRESS,
+        AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS,
+        Address,
+        BASE_DOMAIN_ID,
+        BASE_MESSAGE_TRANSMITTER_ADDRESS,
+        BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+        CODEX_DOMAIN_ID,
+        CODEX_ID,
+        CODEX_TESTNET_ID,
+        ETHEREUM_DOMAIN_ID,
+        ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS,
+        ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+        HYPEREVM_DOMAIN_ID,
+        HYPEREVM_ID,
+        HYPEREVM_TESTNET_ID,
+        INK_DOMAIN_ID,
+        INK_ID,
+        INK_SEPOLIA_ID,
+        LINEA_DOMAIN_ID,
+        LINEA_ID,
+        LINEA_SEPOLIA_ID,
+        MESSAGE_TRANSMITTER_ADDRESS,
+        MESSAGE_TRANSMITTER_ADDRESS_TESTNET,
+        OPTIMISM_DOMAIN_ID,
+        OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS,
+        OPTIMISM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS,
+        PLUME_DOMAIN_ID,
+        PLUME_ID,
+        PLUME_TESTNET_ID,
+        POLYGON_AMOY_MESSAGE_TRANSMITTER_ADDRESS,
+        POLYGON_CCTP_MESSAGE_TRANSMITTER,
+        POLYGON_DOMAIN_ID,
+        SEI_DOMAIN_ID,
+        SEI_ID,
+        SEI_TESTNET_ID,
+        SOLANA_DEVNET_ID,
+        SOLANA_DEVNET_USDC_TOKEN,
+        SOLANA_DOMAIN_ID,
+        SOLANA_MAINNET_ID,
+        SOLANA_MAINNET_USDC_TOKEN,
+        SONIC_DOMAIN_ID,
+        SONIC_ID,
+        SONIC_TESTNET_ID,
+        UNICHAIN_CCTP_MESSAGE_TRANSMITTER,
+        UNICHAIN_DOMAIN_ID,
+        WORLD_CHAIN_DOMAIN_ID,
+        WORLD_CHAIN_ID,
+        WORLD_CHAIN_SEPOLIA_ID,
+        error::{Error, Result},
+        token_messenger::*,
+    },
+    alloy_chains::{Chain, NamedChain},
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeMap,
+        path::Path,
+        sync::{Arc, LazyLock, PoisonError, RwLock},
+        time::Duration,
+    },
+};
+
+static CHAIN_REGISTRY: LazyLock<RwLock<Arc<ChainRegistry>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(ChainRegistry::builtin())));
+
+/// CCTP deployment of one chain. Fields left `None` are unknown, the matching
+/// [`CctpChain`](crate::CctpChain) accessor fails for them and the chain is
+/// not supported until the entry is complete
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// The chain id, for non EVM chains the id of the crate's [`Chain`]
+    /// constant, e.g. [`SOLANA_MAINNET`](crate::SOLANA_MAINNET)
+    pub chain_id: u64,
+    /// The CCTP domain id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<u32>,
+    /// The `TokenMessenger` contract or program
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_messenger: Option<Address>,
+    /// The `MessageTransmitter` contract or program
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_transmitter: Option<Address>,
+    /// The USDC token contract or mint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usdc: Option<Address>,
+    /// Whether the chain attests through the Iris sandbox, defaults to
+    /// [`NamedChain::is_testnet`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<bool>,
+    /// Average time to attestation
+    /// <https://developers.circle.com/stablecoins/required-block-confirmations>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_average_time_seconds: Option<u64>,
+    /// Confirmations to wait for after sending a transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+    /// How long to wait for `confirmations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmation_timeout_seconds: Option<u64>,
+}
+
+impl ChainConfig {
+    /// An entry for `chain` with every field unknown
+    pub fn new(chain: impl Into<Chain>) -> Self {
+        Self {
+            chain_id: chain.into().id(),
+            ..Default::default()
+        }
+    }
+
+    /// The [`Chain`] of [`ChainConfig::chain_id`]
+    pub fn chain(&self) -> Chain {
+        Chain::from_id(self.chain_id)
+    }
+
+    /// Whether every field needed to bridge is known
+    pub fn is_complete(&self) -> bool {
+        self.domain_id.is_some()
+            && self.token_messenger.is_some()
+            && self.message_transmitter.is_some()
+            && self.usdc.is_some()
+            && self.confirmation_average_time_seconds.is_some()
+    }
+
+    /// Overwrites the fields that are set in `other`
+    pub fn merge(&mut self, other: &ChainConfig) {
+        self.domain_id = other.domain_id.or(self.domain_id);
+        self.token_messenger = other.token_messenger.or(self.token_messenger);
+        self.message_transmitter = other.message_transmitter.or(self.message_transmitter);
+        self.usdc = other.usdc.or(self.usdc);
+        self.sandbox = other.sandbox.or(self.sandbox);
+        self.confirmation_average_time_seconds = other
+            .confirmation_average_time_seconds
+            .or(self.confirmation_average_time_seconds);
+        self.confirmations = other.confirmations.or(self.confirmations);
+        self.confirmation_timeout_seconds = other
+            .confirmation_timeout_seconds
+            .or(self.confirmation_timeout_seconds);
+    }
+
+    /// Confirmations to wait for and how long to wait
+    pub fn confirmation_config(&self) -> (u64, Duration) {
+        (
+            self.confirmations.unwrap_or(1),
+            self.confirmation_timeout_seconds
+                .map_or(crate::DEFAULT_CONFIRMATION_TIMEOUT, Duration::from_secs),
+        )
+    }
+}
+
+/// File layout of a [`ChainRegistry`]
+#[derive(Default, Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    chains: Vec<ChainConfig>,
+}
+
+/// [`ChainConfig`] of every known chain, keyed by chain id
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChainRegistry {
+    chains: BTreeMap<u64, ChainConfig>,
+}
+
+impl ChainRegistry {
+    /// The chains shipped with the crate
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for config in builtin_chains() {
+            registry.insert(config);
+        }
+        registry
+    }
+
+    /// The entry of `chain`, `None` for chains the registry does not know
+    pub fn get(&self, chain: &Chain) -> Option<&ChainConfig> {
+        self.chains.get(&chain.id())
+    }
+
+    /// Adds `config`, merging it into the existing entry of its chain
+    pub fn insert(&mut self, config: ChainConfig) {
+        self.chains
+            .entry(config.chain_id)
+            .and_modify(|existing| existing.merge(&config))
+            .or_insert(config);
+    }
+
+    /// Adds every entry of `other`, see [`ChainRegistry::insert`]
+    pub fn merge(&mut self, other: ChainRegistry) {
+        for config in other.chains.into_values() {
+            self.insert(config);
+        }
+    }
+
+    /// Every entry, ordered by chain id
+    pub fn chains(&self) -> impl Iterator<Item = &ChainConfig> {
+        self.chains.values()
+    }
+
+    /// Parses `{"chains": [...]}`
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::from_file_contents(serde_json::from_str(json)?))
+    }
+
+    /// Parses `[[chains]]` tables
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        let file = toml::from_str(toml)
+            .map_err(|e| Error::InvalidConfig(format!("invalid chain registry: {e}")))?;
+        Ok(Self::from_file_contents(file))
+    }
+
+    /// Reads a `.json` or `.toml` file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::InvalidConfig(format!("{}: {e}", path.display())))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            _ => Err(Error::InvalidConfig(format!(
+                "{}: expected a .json or .toml chain registry",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Serializes as `{"chains": [...]}`, see [`ChainRegistry::from_json`]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.to_file_contents())?)
+    }
+
+    /// Serializes as `[[chains]]` tables, see [`ChainRegistry::from_toml`]
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(&self.to_file_contents())
+            .map_err(|e| Error::InvalidConfig(format!("invalid chain registry: {e}")))
+    }
+
+    fn from_file_contents(file: RegistryFile) -> Self {
+        let mut registry = Self::default();
+        for config in file.chains {
+            registry.insert(config);
+        }
+        registry
+    }
+
+    fn to_file_contents(&self) -> RegistryFile {
+        RegistryFile {
+            chains: self.chains.values().copied().collect(),
+        }
+    }
+
+    /// A snapshot of the registry used by [`CctpChain`](crate::CctpChain),
+    /// later changes to the global registry do not affect it
+    pub fn global() -> Arc<Self> {
+        Arc::clone(
+            &CHAIN_REGISTRY
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Replaces the registry used by [`CctpChain`](crate::CctpChain)
+    pub fn set_global(registry: ChainRegistry) {
+        *CHAIN_REGISTRY
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(registry);
+    }
+
+    /// Merges `overrides` into the registry used by
+    /// [`CctpChain`](crate::CctpChain)
+    pub fn override_global(overrides: ChainRegistry) {
+        let mut global = CHAIN_REGISTRY
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        Arc::make_mut(&mut global).merge(overrides);
+    }
+
+    /// Merges the registry file at `path` into the global registry
+    pub fn load_overrides(path: impl AsRef<Path>) -> Result<()> {
+        Self::override_global(Self::from_file(path)?);
+        Ok(())
+    }
+}
+
+/// The global entry of `chain`
+pub(crate) fn chain_config(chain: &Chain) -> Option<ChainConfig> {
+    CHAIN_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(chain)
+        .copied()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn builtin(
+    chain: NamedChain,
+    domain_id: u32,
+    token_messenger: impl Into<Address>,
+    message_transmitter: impl Into<Address>,
+    usdc: impl Into<Address>,
+    confirmation_average_time_seconds: u64,
+    confirmations: Option<(u64, u64)>,
+) -> ChainConfig {
+    ChainConfig {
+        chain_id: chain as u64,
+        domain_id: Some(domain_id),
+        token_messenger: Some(token_messenger.into()),
+        message_transmitter: Some(message_transmitter.into()),
+        usdc: Some(usdc.into()),
+        sandbox: Some(chain.is_testnet()),
+        confirmation_average_time_seconds: Some(confirmation_average_time_seconds),
+        confirmations: confirmations.map(|(confirmations, _)| confirmations),
+        confirmation_timeout_seconds: confirmations.map(|(_, timeout)| timeout),
+    }
+}
+
+/// A chain with the shared CCTP v2 contracts, addressed by id as it may have
+/// no [`NamedChain`] variant
+fn builtin_v2(
+    chain_id: u64,
+    testnet: bool,
+    domain_id: u32,
+    usdc: alloy_primitives::Address,
+    confirmation_average_time_seconds: u64,
+    confirmations: Option<(u64, u64)>,
+) -> ChainConfig {
+    let (token_messenger, message_transmitter) = if testnet {
+        (
+            TOKEN_MESSENGER_CONTRACT_TESTNET,
+            MESSAGE_TRANSMITTER_ADDRESS_TESTNET,
+        )
+    } else {
+        (TOKEN_MESSENGER_CONTRACT, MESSAGE_TRANSMITTER_ADDRESS)
+    };
+    ChainConfig {
+        chain_id,
+        domain_id: Some(domain_id),
+        token_messenger: Some(token_messenger.into()),
+        message_transmitter: Some(message_transmitter.into()),
+        usdc: Some(usdc.into()),
+        sandbox: Some(testnet),
+        confirmation_average_time_seconds: Some(confirmation_average_time_seconds),
+        confirmations: confirmations.map(|(confirmations, _)| confirmations),
+        confirmation_timeout_seconds: confirmations.map(|(_, timeout)| timeout),
+    }
+}
+
+#[rustfmt::skip]
+fn builtin_chains() -> Vec<ChainConfig> {
+    use NamedChain::*;
+
+    let solana = |chain_id: u64, usdc: solana_pubkey::Pubkey, sandbox: bool| ChainConfig {
+        chain_id,
+        domain_id: Some(SOLANA_DOMAIN_ID),
+        token_messenger: Some(nitrogen_circle_token_messenger_minter_v2_encoder::id().into()),
+        message_transmitter: Some(nitrogen_circle_message_transmitter_v2_encoder::id().into()),
+        usdc: Some(Address::from(usdc)),
+        sandbox: Some(sandbox),
+        confirmation_average_time_seconds: Some(4),
+        confirmations: Some(2),
+        confirmation_timeout_seconds: Some(4),
+    };
+
+    vec![
+        builtin(Mainnet, ETHEREUM_DOMAIN_ID, ETHEREUM_TOKEN_MESSENGER_ADDRESS, ETHEREUM_MESSAGE_TRANSMITTER_ADDRESS, ETHEREUM_USDC_CONTRACT, 19 * 60, Some((2, 300))),
+        builtin(Arbitrum, ARBITRUM_DOMAIN_ID, ARBITRUM_TOKEN_MESSENGER_ADDRESS, ARBITRUM_MESSAGE_TRANSMITTER_ADDRESS, ARBITRUM_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin(Avalanche, AVALANCHE_DOMAIN_ID, AVALANCHE_TOKEN_MESSENGER_ADDRESS, AVALANCHE_MESSAGE_TRANSMITTER_ADDRESS, AVALANCHE_USDC_CONTRACT, 20, Some((3, 120))),
+        builtin(Base, BASE_DOMAIN_ID, BASE_TOKEN_MESSENGER_ADDRESS, BASE_MESSAGE_TRANSMITTER_ADDRESS, BASE_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin(Optimism, OPTIMISM_DOMAIN_ID, OPTIMISM_TOKEN_MESSENGER_ADDRESS, OPTIMISM_MESSAGE_TRANSMITTER_ADDRESS, OPTIMISM_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin(Polygon, POLYGON_DOMAIN_ID, POLYGON_CCTP_TOKEN_MESSENGER, POLYGON_CCTP_MESSAGE_TRANSMITTER, POLYGON_USDC_CONTRACT, 8 * 60, Some((15, 180))),
+        builtin(Unichain, UNICHAIN_DOMAIN_ID, UNICHAIN_CCTP_TOKEN_MESSENGER, UNICHAIN_CCTP_MESSAGE_TRANSMITTER, UNICHAIN_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        // Testnets
+        builtin(Sepolia, ETHEREUM_DOMAIN_ID, ETHEREUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS, ETHEREUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS, ETHEREUM_SEPOLIA_USDC_CONTRACT, 60, None),
+        builtin(ArbitrumSepolia, ARBITRUM_DOMAIN_ID, ARBITRUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS, ARBITRUM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS, ARBITRUM_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin(BaseSepolia, BASE_DOMAIN_ID, BASE_SEPOLIA_TOKEN_MESSENGER_ADDRESS, BASE_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS, BASE_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin(AvalancheFuji, AVALANCHE_DOMAIN_ID, AVALANCHE_FUJI_TOKEN_MESSENGER_ADDRESS, AVALANCHE_FUJI_MESSAGE_TRANSMITTER_ADDRESS, AVALANCHE_FUJI_USDC_CONTRACT, 20, None),
+        builtin(OptimismSepolia, OPTIMISM_DOMAIN_ID, OPTIMISM_SEPOLIA_TOKEN_MESSENGER_ADDRESS, OPTIMISM_SEPOLIA_MESSAGE_TRANSMITTER_ADDRESS, OPTIMISM_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin(PolygonAmoy, POLYGON_DOMAIN_ID, POLYGON_AMOY_TOKEN_MESSENGER_ADDRESS, POLYGON_AMOY_MESSAGE_TRANSMITTER_ADDRESS, POLYGON_AMOY_USDC_CONTRACT, 20, None),
+        builtin_v2(LINEA_ID, false, LINEA_DOMAIN_ID, LINEA_USDC_CONTRACT, 8 * 60 * 60, Some((1, 120))),
+        builtin_v2(CODEX_ID, false, CODEX_DOMAIN_ID, CODEX_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin_v2(SONIC_ID, false, SONIC_DOMAIN_ID, SONIC_USDC_CONTRACT, 8, Some((1, 60))),
+        builtin_v2(WORLD_CHAIN_ID, false, WORLD_CHAIN_DOMAIN_ID, WORLD_CHAIN_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin_v2(SEI_ID, false, SEI_DOMAIN_ID, SEI_USDC_CONTRACT, 5, Some((1, 60))),
+        builtin_v2(HYPEREVM_ID, false, HYPEREVM_DOMAIN_ID, HYPEREVM_USDC_CONTRACT, 5, Some((1, 60))),
+        builtin_v2(INK_ID, false, INK_DOMAIN_ID, INK_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin_v2(PLUME_ID, false, PLUME_DOMAIN_ID, PLUME_USDC_CONTRACT, 19 * 60, Some((1, 120))),
+        builtin_v2(LINEA_SEPOLIA_ID, true, LINEA_DOMAIN_ID, LINEA_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin_v2(CODEX_TESTNET_ID, true, CODEX_DOMAIN_ID, CODEX_TESTNET_USDC_CONTRACT, 20, None),
+        builtin_v2(SONIC_TESTNET_ID, true, SONIC_DOMAIN_ID, SONIC_TESTNET_USDC_CONTRACT, 8, None),
+        builtin_v2(WORLD_CHAIN_SEPOLIA_ID, true, WORLD_CHAIN_DOMAIN_ID, WORLD_CHAIN_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin_v2(SEI_TESTNET_ID, true, SEI_DOMAIN_ID, SEI_TESTNET_USDC_CONTRACT, 5, None),
+        builtin_v2(HYPEREVM_TESTNET_ID, true, HYPEREVM_DOMAIN_ID, HYPEREVM_TESTNET_USDC_CONTRACT, 5, None),
+        builtin_v2(INK_SEPOLIA_ID, true, INK_DOMAIN_ID, INK_SEPOLIA_USDC_CONTRACT, 20, None),
+        builtin_v2(PLUME_TESTNET_ID, true, PLUME_DOMAIN_ID, PLUME_TESTNET_USDC_CONTRACT, 20, None),
+        solana(SOLANA_MAINNET_ID, SOLANA_MAINNET_USDC_TOKEN, false),
+        solana(SOLANA_DEVNET_ID, SOLANA_DEVNET_USDC_TOKEN, true),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINEA_TOML: &str = r#"
+        [[chains]]
+        chain_id = 59144
+        domain_id = 11
+        token_messenger = "0x28b5a0e9C621a5BadaA536219b3a228C8168cf5d"
+        message_transmitter = "0x81D40F21F12A8F0E3252Bccb954D722d4c464B64"
+        usdc = "0x176211869cA2b568f2A7D4EE941E073a821EE1ff"
+        confirmation_average_time_seconds = 60
+    "#;
+
+    #[test]
+    fn test_from_toml() -> anyhow::Result<()> {
+        let registry = ChainRegistry::from_toml(LINEA_TOML)?;
+        let linea = registry.get(&Chain::from_id(59144)).unwrap();
+        assert_eq!(linea.domain_id, Some(11));
+        let usdc: alloy_primitives::Address = linea.usdc.unwrap().try_into()?;
+        assert_eq!(
+            usdc,
+            alloy_primitives::address!("0x176211869cA2b568f2A7D4EE941E073a821EE1ff")
+        );
+        assert_eq!(linea.confirmations, None);
+        assert_eq!(linea.confirmation_config().0, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_roundtrip() -> anyhow::Result<()> {
+        let registry = ChainRegistry::builtin();
+        assert_eq!(ChainRegistry::from_json(&registry.to_json()?)?, registry);
+        assert_eq!(ChainRegistry::from_toml(&registry.to_toml()?)?, registry);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_address() {
+        let json = r#"{"chains": [{"chain_id": 1, "usdc": "not an address"}]}"#;
+        assert!(matches!(
+            ChainRegistry::from_json(json),
+            Err(Error::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_override_fields() -> anyhow::Result<()> {
+        let mut registry = ChainRegistry::builtin();
+        registry.merge(ChainRegistry::from_json(
+            r#"{"chains": [{"chain_id": 1, "confirmations": 12}]}"#,
+        )?);
+        let mainnet = registry.get(&NamedChain::Mainnet.into()).unwrap();
+        assert_eq!(
+            mainnet.confirmation_config(),
+            (12, Duration::from_secs(300))
+        );
+        assert_eq!(mainnet.domain_id, Some(ETHEREUM_DOMAIN_ID));
+        Ok(())
+    }
+
+    #[test]
+    fn test_override_completes_entry() -> anyhow::Result<()> {
+        let chain = Chain::from_id(0x00c0_ffee);
+        let mut registry = ChainRegistry::builtin();
+        assert!(registry.get(&chain).is_none());
+        registry.merge(ChainRegistry::from_json(
+            r#"{"chains": [{"chain_id": 12648430, "domain_id": 99}]}"#,
+        )?);
+        let config = registry.get(&chain).unwrap();
+        assert!(!config.is_complete());
+        assert_eq!(config.domain_id, Some(99));
+        assert_eq!(config.token_messenger, None);
+
+        registry.merge(ChainRegistry::from_toml(
+            r#"
+            [[chains]]
+            chain_id = 12648430
+            token_messenger = "0x28b5a0e9C621a5BadaA536219b3a228C8168cf5d"
+            message_transmitter = "0x81D40F21F12A8F0E3252Bccb954D722d4c464B64"
+            usdc = "0x176211869cA2b568f2A7D4EE941E073a821EE1ff"
+            confirmation_average_time_seconds = 60
+            "#,
+        )?);
+        let config = registry.get(&chain).unwrap();
+        assert!(config.is_complete());
+        assert_eq!(config.domain_id, Some(99));
+        assert_eq!(config.chain(), chain);
+        Ok(())
+    }
+}
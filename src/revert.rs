@@ -0,0 +1,261 @@
+//! Reverts of the CCTP v2 contracts and programs.
+//!
+//! The EVM contracts revert with `require` strings, the Solana programs with
+//! Anchor errors whose name is in the transaction logs.
+use {
+    alloy_json_rpc::RpcError,
+    alloy_sol_types::{Revert, SolError},
+    alloy_transport::TransportErrorKind,
+    nitrogen_circle_message_transmitter_v2_encoder::ID as MESSAGE_TRANSMITTER_PROGRAM_ID,
+    nitrogen_circle_token_messenger_minter_v2_encoder::ID as TOKEN_MESSENGER_PROGRAM_ID,
+    solana_pubkey::Pubkey,
+    solana_rpc_client_api::{
+        client_error::{Error as ClientError, ErrorKind as ClientErrorKind},
+        request::{RpcError as SolanaRpcError, RpcResponseErrorData},
+    },
+    thiserror::Error,
+};
+
+/// Prefix of the revert reason in the message of `eth_estimateGas` and
+/// `eth_call` errors
+const EXECUTION_REVERTED: &str = "execution reverted: ";
+
+/// Prefix of the error name in the log of a failed Anchor instruction
+const ANCHOR_ERROR_CODE: &str = "Error Code: ";
+
+/// Prefix of the program invocation and result logs
+const PROGRAM: &str = "Program ";
+
+/// A decoded revert of the CCTP contracts or programs
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum CctpRevert {
+    #[error("account is on the denylist")]
+    Denylisted,
+    #[error("contract or program is paused")]
+    Paused,
+    #[error("message nonce already used")]
+    NonceAlreadyUsed,
+    #[error("invalid attestation: {0}")]
+    InvalidAttestation(String),
+    #[error("max fee must be less than the amount")]
+    MaxFeeTooHigh,
+    #[error("max fee is below the minimum fee")]
+    InsufficientMaxFee,
+    #[error("burn amount exceeds the per transaction limit")]
+    BurnAmountExceedsLimit,
+    #[error("burn token not supported")]
+    UnsupportedBurnToken,
+    #[error("caller is not the message's destination caller")]
+    InvalidDestinationCaller,
+    #[error("message expired and must be re-signed")]
+    MessageExpired,
+    /// A reason without a typed variant
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CctpRevert {
+    /// Maps a `require` reason of the v2 `TokenMessenger`,
+    /// `MessageTransmitter` or USDC contracts
+    pub fn from_reason(reason: &str) -> Self {
+        let reason = reason.trim();
+        match reason {
+            "Denylistable: account is on denylist" | "Blacklistable: account is blacklisted" => {
+                Self::Denylisted
+            }
+            "Pausable: paused" => Self::Paused,
+            "Nonce already used" => Self::NonceAlreadyUsed,
+            "Max fee must be less than amount" => Self::MaxFeeTooHigh,
+            "Insufficient max fee" => Self::InsufficientMaxFee,
+            "Burn amount exceeds per tx limit" => Self::BurnAmountExceedsLimit,
+            "Burn token not supported" => Self::UnsupportedBurnToken,
+            "Invalid caller for message" => Self::InvalidDestinationCaller,
+            "Message expired and must be re-signed" => Self::MessageExpired,
+            _ if reason.starts_with("Invalid signature")
+                || reason == "Invalid attestation length" =>
+            {
+                Self::InvalidAttestation(reason.to_string())
+            }
+            _ => Self::Other(reason.to_string()),
+        }
+    }
+
+    /// Decodes `Error(string)` revert data
+    pub fn from_revert_data(data: &[u8]) -> Option<Self> {
+        Revert::abi_decode(data)
+            .ok()
+            .map(|revert| Self::from_reason(revert.reason()))
+    }
+
+    /// Decodes the revert of a failed `eth_call`, `eth_estimateGas` or
+    /// `eth_sendTransaction`
+    pub fn from_rpc_error(error: &RpcError<TransportErrorKind>) -> Option<Self> {
+        let payload = error.as_error_resp()?;
+        if let Some(revert) = payload
+            .as_revert_data()
+            .and_then(|data| Self::from_revert_data(&data))
+        {
+            return Some(revert);
+        }
+        payload
+            .message
+            .split_once(EXECUTION_REVERTED)
+            .map(|(_, reason)| Self::from_reason(reason))
+    }
+
+    /// Maps the name of an Anchor error thrown by `program`, the v2
+    /// `TokenMessengerMinter` or `MessageTransmitter`, as in their IDLs
+    pub fn from_anchor_error(program: &Pubkey, name: &str) -> Self {
+        match (*program, name) {
+            (MESSAGE_TRANSMITTER_PROGRAM_ID, "ProgramPaused") => Self::Paused,
+            (MESSAGE_TRANSMITTER_PROGRAM_ID, "NonceAlreadyUsed") => Self::NonceAlreadyUsed,
+            (MESSAGE_TRANSMITTER_PROGRAM_ID, "InvalidDestinationCaller") => {
+                Self::InvalidDestinationCaller
+            }
+            (
+                MESSAGE_TRANSMITTER_PROGRAM_ID,
+                "InvalidSignatureOrderOrDupe"
+                | "InvalidAttesterSignature"
+                | "InvalidAttestationLength"
+                | "InvalidSignatureRecoveryId"
+                | "InvalidSignatureSValue",
+            ) => Self::InvalidAttestation(name.to_string()),
+            (TOKEN_MESSENGER_PROGRAM_ID, "ProgramPaused") => Self::Paused,
+            (TOKEN_MESSENGER_PROGRAM_ID, "BurnAmountExceeded") => Self::BurnAmountExceedsLimit,
+            (TOKEN_MESSENGER_PROGRAM_ID, "DenylistedAccount") => Self::Denylisted,
+            (TOKEN_MESSENGER_PROGRAM_ID, "MaxFeeExceedsAmount") => Self::MaxFeeTooHigh,
+            (TOKEN_MESSENGER_PROGRAM_ID, "InsufficientMaxFee") => Self::InsufficientMaxFee,
+            (TOKEN_MESSENGER_PROGRAM_ID, "InvalidTokenMint") => Self::UnsupportedBurnToken,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+
+    /// Finds the Anchor error in the logs of a failed Solana transaction,
+    /// attributed to the innermost program invoked when it was thrown
+    pub fn from_solana_logs<S: AsRef<str>>(logs: &[S]) -> Option<Self> {
+        let mut invoked: Vec<Pubkey> = Vec::new();
+        for log in logs {
+            let log = log.as_ref();
+            if let Some(rest) = log.strip_prefix(PROGRAM) {
+                let mut words = rest.split_whitespace();
+                match (words.next().map(str::parse::<Pubkey>), words.next()) {
+                    (Some(Ok(program)), Some("invoke")) => invoked.push(program),
+                    (Some(Ok(_)), Some("success")) => {
+                        invoked.pop();
+                    }
+                    _ => {}
+                }
+            }
+            if let Some((_, rest)) = log.split_once(ANCHOR_ERROR_CODE) {
+                let name = rest.split('.').next()?.trim();
+                return match (invoked.last(), name.is_empty()) {
+                    (_, true) => None,
+                    (Some(program), false) => Some(Self::from_anchor_error(program, name)),
+                    (None, false) => Some(Self::Other(name.to_string())),
+                };
+            }
+        }
+        None
+    }
+
+    /// Decodes the revert of a Solana transaction that failed its preflight
+    /// simulation
+    pub fn from_solana_client_error(error: &ClientError) -> Option<Self> {
+        match error.kind() {
+            ClientErrorKind::RpcError(SolanaRpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                ..
+            }) => Self::from_solana_logs(result.logs.as_deref()?),
+            _ => None,
+        }
+    }
+
+    /// Decodes the revert of any error whose source chain holds a Solana
+    /// client error, such as `nitrogen_instruction_builder::Error`
+    pub fn from_solana_error_source(error: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        let mut source = Some(error);
+        while let Some(error) = source {
+            if let Some(client) = error.downcast_ref::<ClientError>() {
+                return Self::from_solana_client_error(client);
+            }
+            source = error.source();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, rstest::rstest};
+
+    #[rstest]
+    #[case("Denylistable: account is on denylist", CctpRevert::Denylisted)]
+    #[case("Pausable: paused", CctpRevert::Paused)]
+    #[case("Nonce already used", CctpRevert::NonceAlreadyUsed)]
+    #[case("Max fee must be less than amount", CctpRevert::MaxFeeTooHigh)]
+    #[case("Burn amount exceeds per tx limit", CctpRevert::BurnAmountExceedsLimit)]
+    #[case(
+        "Invalid signature: not attester",
+        CctpRevert::InvalidAttestation("Invalid signature: not attester".to_string())
+    )]
+    #[case("Amount must be nonzero", CctpRevert::Other("Amount must be nonzero".to_string()))]
+    fn test_from_reason(#[case] reason: &str, #[case] expected: CctpRevert) {
+        assert_eq!(CctpRevert::from_reason(reason), expected);
+        let data = Revert::from(reason).abi_encode();
+        assert_eq!(CctpRevert::from_revert_data(&data), Some(expected));
+    }
+
+    #[test]
+    fn test_from_solana_logs() {
+        let logs = [
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program CCTPV2Sm4AdWt5296sk4P66VBZ7bEhcARwFaaS9YPbeC invoke [1]",
+            "Program log: Instruction: ReceiveMessage",
+            "Program log: AnchorError thrown in \
+             programs/v2/message-transmitter-v2/src/instructions/receive_message.rs:188. Error \
+             Code: InvalidAttesterSignature. Error Number: 6027. Error Message: Invalid attester \
+             signature.",
+            "Program CCTPV2Sm4AdWt5296sk4P66VBZ7bEhcARwFaaS9YPbeC consumed 41394 of 199850 \
+             compute units",
+            "Program CCTPV2Sm4AdWt5296sk4P66VBZ7bEhcARwFaaS9YPbeC failed: custom program error: \
+             0x178b",
+        ];
+        assert_eq!(
+            CctpRevert::from_solana_logs(&logs),
+            Some(CctpRevert::InvalidAttestation(
+                "InvalidAttesterSignature".to_string()
+            ))
+        );
+        assert_eq!(CctpRevert::from_solana_logs(&logs[..4]), None);
+    }
+
+    #[test]
+    fn test_from_solana_logs_program() {
+        let logs = [
+            "Program CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe invoke [1]",
+            "Program log: Instruction: DepositForBurn",
+            "Program log: AnchorError thrown in \
+             programs/v2/token-messenger-minter-v2/src/token_messenger_v2/instructions/\
+             deposit_for_burn.rs:197. Error Code: InsufficientMaxFee. Error Number: 6029. Error \
+             Message: Insufficient max fee.",
+            "Program CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe failed: custom program error: \
+             0x178d",
+        ];
+        assert_eq!(
+            CctpRevert::from_solana_logs(&logs),
+            Some(CctpRevert::InsufficientMaxFee)
+        );
+        // the same name thrown by another program is not a CCTP revert
+        let other = logs.map(|log| {
+            log.replace(
+                "CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe",
+                "11111111111111111111111111111111",
+            )
+        });
+        assert_eq!(
+            CctpRevert::from_solana_logs(&other),
+            Some(CctpRevert::Other("InsufficientMaxFee".to_string()))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Safe (Gnosis Safe) transaction batches.
+//!
+//! A Safe executes several calls atomically by delegate calling
+//! `MultiSendCallOnly` with the calls packed into one `bytes` argument. The
+//! same calls can be exported for the Safe{Wallet} Transaction Builder app,
+//! which builds that batch itself.
+use {
+    alloy_primitives::{Address, Bytes, U256, address},
+    alloy_sol_types::{SolCall, sol},
+    serde::{Deserialize, Serialize},
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+/// `MultiSendCallOnly` v1.4.1, same address on every EVM chain
+/// <https://github.com/safe-global/safe-deployments>
+pub const MULTI_SEND_CALL_ONLY_ADDRESS: Address =
+    address!("0x9641d764fc13c8B624c04430C7356C1C7C8102e2");
+
+/// The Transaction Builder version the exported batches follow
+pub const TX_BUILDER_VERSION: &str = "1.16.5";
+
+sol! {
+    #[allow(missing_docs)]
+    interface IMultiSend {
+        function multiSend(bytes memory transactions) external payable;
+    }
+}
+
+/// How a Safe executes a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SafeOperation {
+    Call = 0,
+    DelegateCall = 1,
+}
+
+/// One call of a Safe batch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SafeCall {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// A transaction for a Safe to execute, e.g. through `execTransaction`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SafeTransaction {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub operation: SafeOperation,
+}
+
+impl SafeTransaction {
+    /// A delegate call of `MultiSendCallOnly` executing `calls` in order
+    pub fn multi_send(calls: &[SafeCall]) -> Self {
+        Self {
+            to: MULTI_SEND_CALL_ONLY_ADDRESS,
+            value: U256::ZERO,
+            data: IMultiSend::multiSendCall {
+                transactions: encode_multi_send(calls),
+            }
+            .abi_encode()
+            .into(),
+            operation: SafeOperation::DelegateCall,
+        }
+    }
+}
+
+/// Packs `calls` for `multiSend`: operation, target, value, data length and
+/// data of each call
+pub fn encode_multi_send(calls: &[SafeCall]) -> Bytes {
+    let mut packed = Vec::new();
+    for call in calls {
+        packed.push(SafeOperation::Call as u8);
+        packed.extend_from_slice(call.to.as_slice());
+        packed.extend_from_slice(&call.value.to_be_bytes::<32>());
+        packed.extend_from_slice(&U256::from(call.data.len()).to_be_bytes::<32>());
+        packed.extend_from_slice(&call.data);
+    }
+    packed.into()
+}
+
+/// A Transaction Builder batch file
+/// <https://help.safe.global/en/articles/40841-transaction-builder>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBatch {
+    pub version: String,
+    pub chain_id: String,
+    /// Milliseconds since the Unix epoch
+    pub created_at: u64,
+    pub meta: BatchMeta,
+    pub transactions: Vec<BatchTransaction>,
+}
+
+/// The metadata of a [`TransactionBatch`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchMeta {
+    pub name: String,
+    pub description: String,
+    pub tx_builder_version: String,
+    pub created_from_safe_address: Address,
+}
+
+/// One call of a [`TransactionBatch`], with raw calldata
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTransaction {
+    pub to: Address,
+    /// Wei as a decimal string
+    pub value: String,
+    pub data: Bytes,
+}
+
+impl TransactionBatch {
+    /// The batch of `calls` for `safe` on the chain with id `chain_id`
+    pub fn new(
+        chain_id: u64,
+        safe: Address,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        calls: &[SafeCall],
+    ) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Self {
+            version: "1.0".to_string(),
+            chain_id: chain_id.to_string(),
+            created_at,
+            meta: BatchMeta {
+                name: name.into(),
+                description: description.into(),
+                tx_builder_version: TX_BUILDER_VERSION.to_string(),
+                created_from_safe_address: safe,
+            },
+            transactions: calls
+                .iter()
+                .map(|call| BatchTransaction {
+                    to: call.to,
+                    value: call.value.to_string(),
+                    data: call.data.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, alloy_primitives::hex};
+
+    #[test]
+    fn test_encode_multi_send() {
+        let calls = [
+            SafeCall {
+                to: Address::repeat_byte(0x11),
+                value: U256::ZERO,
+                data: Bytes::from_static(&[0xab, 0xcd]),
+            },
+            SafeCall {
+                to: Address::repeat_byte(0x22),
+                value: U256::from(1),
+                data: Bytes::new(),
+            },
+        ];
+        let packed = encode_multi_send(&calls);
+        assert_eq!(packed.len(), 2 * (1 + 20 + 32 + 32) + 2);
+        assert_eq!(
+            hex::encode(&packed[..1 + 20 + 32 + 32 + 2]),
+            format!(
+                "00{}{}{}02abcd",
+                "11".repeat(20),
+                "00".repeat(32),
+                "00".repeat(31)
+            )
+        );
+        let tx = SafeTransaction::multi_send(&calls);
+        assert_eq!(tx.to, MULTI_SEND_CALL_ONLY_ADDRESS);
+        assert_eq!(tx.operation, SafeOperation::DelegateCall);
+        let decoded = IMultiSend::multiSendCall::abi_decode(&tx.data).unwrap();
+        assert_eq!(decoded.transactions, packed);
+    }
+
+    #[test]
+    fn test_transaction_batch_json() -> anyhow::Result<()> {
+        let safe = Address::repeat_byte(0x33);
+        let batch = TransactionBatch::new(8453, safe, "burn", "", &[SafeCall {
+            to: Address::repeat_byte(0x11),
+            value: U256::ZERO,
+            data: Bytes::from_static(&[0xab]),
+        }]);
+        let json = serde_json::to_value(&batch)?;
+        assert_eq!(json["chainId"], "8453");
+        assert_eq!(json["meta"]["createdFromSafeAddress"], safe.to_string());
+        assert_eq!(json["transactions"][0]["value"], "0");
+        assert_eq!(json["transactions"][0]["data"], "0xab");
+        assert_eq!(serde_json::from_value::<TransactionBatch>(json)?, batch);
+        Ok(())
+    }
+}
@@ -15,18 +15,23 @@ pub const TOKEN_MESSENGER_CONTRACT_TESTNET: Address =
 pub const ARBITRUM_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT;
 pub const ARBITRUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const AVALANCHE_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT;
+pub const AVALANCHE_FUJI_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const BASE_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT;
 pub const BASE_SEPOLIA_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const ETHEREUM_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT;
 pub const ETHEREUM_SEPOLIA_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const OPTIMISM_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT;
+pub const OPTIMISM_SEPOLIA_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const POLYGON_CCTP_TOKEN_MESSENGER: Address = TOKEN_MESSENGER_CONTRACT;
+pub const POLYGON_AMOY_TOKEN_MESSENGER_ADDRESS: Address = TOKEN_MESSENGER_CONTRACT_TESTNET;
 pub const UNICHAIN_CCTP_TOKEN_MESSENGER: Address = TOKEN_MESSENGER_CONTRACT;
 
 pub const ARBITRUM_USDC_CONTRACT: Address = address!("0xaf88d065e77c8cC2239327C5EDb3A432268e5831");
 pub const ARBITRUM_SEPOLIA_USDC_CONTRACT: Address =
     address!("0x75faf114eafb1BDbe2F0316DF893fd58CE46AA4d");
 pub const AVALANCHE_USDC_CONTRACT: Address = address!("0xB97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E");
+pub const AVALANCHE_FUJI_USDC_CONTRACT: Address =
+    address!("0x5425890298aed601595a70AB815c96711a31Bc65");
 pub const BASE_USDC_CONTRACT: Address = address!("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
 pub const BASE_SEPOLIA_USDC_CONTRACT: Address =
     address!("0x036CbD53842c5426634e7929541eC2318f3dCF7e");
@@ -37,7 +42,34 @@ pub const OPTIMISM_USDC_CONTRACT: Address = address!("0x0b2C639c533813f4Aa9D7837
 pub const OPTIMISM_SEPOLIA_USDC_CONTRACT: Address =
     address!("0x5fd84259d66Cd46123540766Be93DFE6D43130D7");
 pub const POLYGON_USDC_CONTRACT: Address = address!("0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359");
+pub const POLYGON_AMOY_USDC_CONTRACT: Address =
+    address!("0x41E94Eb019C0762f9Bfcf9Fb1E58725BfB0e7582");
 pub const UNICHAIN_USDC_CONTRACT: Address = address!("0x078D782b760474a361dDA0AF3839290b0EF57AD6");
+pub const LINEA_USDC_CONTRACT: Address = address!("0x176211869cA2b568f2A7D4EE941E073a821EE1ff");
+pub const LINEA_SEPOLIA_USDC_CONTRACT: Address =
+    address!("0xFEce4462D57bD51A6A552365A011b95f0E16d9B7");
+pub const CODEX_USDC_CONTRACT: Address = address!("0xd996633a415985DBd7D6D12f4A4343E31f5037cf");
+pub const CODEX_TESTNET_USDC_CONTRACT: Address =
+    address!("0x6d7f141b6819C2c9CC2f818e6ad549E7Ca090F8f");
+pub const SONIC_USDC_CONTRACT: Address = address!("0x29219dd400f2Bf60E5a23d13Be72B486D4038894");
+pub const SONIC_TESTNET_USDC_CONTRACT: Address =
+    address!("0xA4879Fed32Ecbef99399e5cbC247E533421C4eC6");
+pub const WORLD_CHAIN_USDC_CONTRACT: Address =
+    address!("0x79A02482A880bCE3F13e09Da970dC34db4CD24d1");
+pub const WORLD_CHAIN_SEPOLIA_USDC_CONTRACT: Address =
+    address!("0x66145f38cBAC35Ca6F1Dfb4914dF98F1614aeA88");
+pub const SEI_USDC_CONTRACT: Address = address!("0xe15fC38F6D8c56aF07bbCBe3BAf5708A2Bf42392");
+pub const SEI_TESTNET_USDC_CONTRACT: Address =
+    address!("0x4fCF1784B31630811181f670Aea7A7bEF803eaED");
+pub const HYPEREVM_USDC_CONTRACT: Address = address!("0xb88339CB7199b77E23DB6E890353E22632Ba630f");
+pub const HYPEREVM_TESTNET_USDC_CONTRACT: Address =
+    address!("0x2B3370eE501B4a559b57D449569354196457D8Ab");
+pub const INK_USDC_CONTRACT: Address = address!("0x2D270e6886d130D724215A266106e6832161EAEd");
+pub const INK_SEPOLIA_USDC_CONTRACT: Address =
+    address!("0xFabab97dCE620294D2B0b0e46C68964e326300Ac");
+pub const PLUME_USDC_CONTRACT: Address = address!("0x222365EF19F7947e5484218551B56bb3965Aa7aF");
+pub const PLUME_TESTNET_USDC_CONTRACT: Address =
+    address!("0xcB5f30e335672893c7eb944B374c196392C19D18");
 
 /// The CCTP v1 Token Messenger contract.
 pub struct TokenMessengerContract<P: Provider<Ethereum>> {
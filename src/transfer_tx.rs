@@ -0,0 +1,94 @@
+use {
+    alloy_primitives::TxHash,
+    serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _},
+    solana_signature::Signature,
+    std::{
+        fmt::{Display, Formatter},
+        str::FromStr,
+    },
+};
+
+/// A transaction of a transfer on either chain family, e.g. of a
+/// [`CctpHub`](crate::CctpHub) transfer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferTx {
+    Evm(TxHash),
+    Solana(Signature),
+}
+
+impl From<TxHash> for TransferTx {
+    fn from(hash: TxHash) -> Self {
+        Self::Evm(hash)
+    }
+}
+
+impl From<Signature> for TransferTx {
+    fn from(signature: Signature) -> Self {
+        Self::Solana(signature)
+    }
+}
+
+impl Display for TransferTx {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Evm(hash) => write!(f, "{hash}"),
+            Self::Solana(signature) => write!(f, "{signature}"),
+        }
+    }
+}
+
+impl FromStr for TransferTx {
+    type Err = String;
+
+    /// `0x` hex for EVM, base58 for Solana
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            s.parse().map(Self::Evm).map_err(|e| format!("{e}"))
+        } else {
+            s.parse().map(Self::Solana).map_err(|e| format!("{e}"))
+        }
+    }
+}
+
+impl Serialize for TransferTx {
+    /// The [`Display`] string, `0x` hex for EVM and base58 for Solana
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransferTx {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_tx_display() {
+        let hash = TxHash::repeat_byte(1);
+        assert_eq!(TransferTx::from(hash).to_string(), hash.to_string());
+        let signature = Signature::default();
+        assert_eq!(
+            TransferTx::from(signature).to_string(),
+            signature.to_string()
+        );
+    }
+
+    #[test]
+    fn test_transfer_tx_serde() {
+        for tx in [
+            TransferTx::from(TxHash::repeat_byte(1)),
+            TransferTx::from(Signature::from([2; 64])),
+        ] {
+            let json = serde_json::to_string(&tx).unwrap();
+            assert_eq!(json, format!("\"{tx}\""));
+            assert_eq!(serde_json::from_str::<TransferTx>(&json).unwrap(), tx);
+        }
+    }
+}
@@ -0,0 +1,469 @@
+//! ERC-4337 user operations for smart account burns.
+//!
+//! A [`SmartAccount`] encodes the burn calls and signs the operation, a
+//! [`Bundler`] estimates its gas, submits it and reports the transaction that
+//! included it. Operations follow the EntryPoint v0.7 format.
+use {
+    crate::{Error, Result, SafeCall},
+    alloy_primitives::{Address, B256, Bytes, TxHash, U256, address, bytes, keccak256},
+    alloy_sol_types::{SolCall, SolValue, sol},
+    reqwest::Client,
+    serde::{Deserialize, Serialize, de::DeserializeOwned},
+    serde_json::{Value, json},
+    std::sync::Arc,
+    tracing::debug,
+};
+
+/// The EntryPoint v0.7 deployment, same address on every EVM chain
+pub const ENTRY_POINT_V07_ADDRESS: Address = address!("0x0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// A well formed signature the `SimpleAccount` rejects without reverting,
+/// used to estimate gas before signing
+pub const SIMPLE_ACCOUNT_DUMMY_SIGNATURE: Bytes = bytes!(
+    "fffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c"
+);
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IEntryPoint {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+    }
+
+    #[allow(missing_docs)]
+    interface ISimpleAccount {
+        function executeBatch(address[] calldata dest, uint256[] calldata value, bytes[] calldata func) external;
+    }
+}
+
+/// An ERC-4337 v0.7 user operation, in the form bundlers accept over JSON-RPC
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    /// Deploys `sender` with its first operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// The hash the account signs, for `entry_point` on the chain `chain_id`
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let init_code = self
+            .factory
+            .map(|factory| {
+                [
+                    factory.as_slice(),
+                    self.factory_data.as_deref().unwrap_or_default(),
+                ]
+                .concat()
+            })
+            .unwrap_or_default();
+        let account_gas_limits =
+            B256::from((self.verification_gas_limit << 128) | self.call_gas_limit);
+        let gas_fees = B256::from((self.max_priority_fee_per_gas << 128) | self.max_fee_per_gas);
+        let packed = (
+            self.sender,
+            self.nonce,
+            keccak256(init_code),
+            keccak256(&self.call_data),
+            account_gas_limits,
+            self.pre_verification_gas,
+            gas_fees,
+            keccak256(Bytes::new()),
+        )
+            .abi_encode();
+        keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+    }
+
+    /// Sets the gas limits estimated by a [`Bundler`]
+    pub fn with_gas(mut self, gas: &UserOperationGas) -> Self {
+        self.call_gas_limit = gas.call_gas_limit;
+        self.verification_gas_limit = gas.verification_gas_limit;
+        self.pre_verification_gas = gas.pre_verification_gas;
+        self
+    }
+}
+
+/// The gas limits of `eth_estimateUserOperationGas`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGas {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+}
+
+impl UserOperationGas {
+    /// The gas the operation may use at most
+    pub fn total(&self) -> U256 {
+        self.pre_verification_gas + self.verification_gas_limit + self.call_gas_limit
+    }
+}
+
+/// The result of `eth_getUserOperationReceipt`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: B256,
+    pub success: bool,
+    /// The revert reason when the calls failed
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub receipt: BundleReceipt,
+}
+
+/// The transaction that included a user operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleReceipt {
+    /// The bundle transaction, its logs carry the burn's CCTP message
+    pub transaction_hash: TxHash,
+}
+
+/// A smart account implementation, which executes a batch of calls and signs
+/// the operations sending them
+#[allow(async_fn_in_trait)]
+pub trait SmartAccount {
+    /// The account address, the `sender` of its operations
+    fn address(&self) -> Address;
+
+    /// The EntryPoint the account is bound to
+    fn entry_point(&self) -> Address {
+        ENTRY_POINT_V07_ADDRESS
+    }
+
+    /// The factory and its calldata when the account is not deployed yet
+    fn factory(&self) -> Option<(Address, Bytes)> {
+        None
+    }
+
+    /// The `callData` executing `calls` in order
+    fn execute_batch(&self, calls: &[SafeCall]) -> Bytes;
+
+    /// A signature the account validates without reverting, for estimates
+    fn dummy_signature(&self) -> Bytes;
+
+    /// Signs the operation `hash`, see [`UserOperation::hash`]
+    async fn sign_user_operation(&self, hash: B256) -> Result<Bytes>;
+}
+
+/// The eth-infinitism `SimpleAccount` v0.7, owned by one key which signs the
+/// operation hash as an EIP-191 message
+#[derive(Clone)]
+pub struct SimpleAccount {
+    address: Address,
+    owner: Arc<dyn alloy_signer::Signer + Send + Sync>,
+    entry_point: Address,
+    factory: Option<(Address, Bytes)>,
+}
+
+impl SimpleAccount {
+    pub fn new(address: Address, owner: Arc<dyn alloy_signer::Signer + Send + Sync>) -> Self {
+        Self {
+            address,
+            owner,
+            entry_point: ENTRY_POINT_V07_ADDRESS,
+            factory: None,
+        }
+    }
+
+    /// Sets the EntryPoint the account is bound to
+    pub fn with_entry_point(mut self, entry_point: Address) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+
+    /// Deploys the account through `factory` with its first operation
+    pub fn with_factory(mut self, factory: Address, data: Bytes) -> Self {
+        self.factory = Some((factory, data));
+        self
+    }
+}
+
+impl SmartAccount for SimpleAccount {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn entry_point(&self) -> Address {
+        self.entry_point
+    }
+
+    fn factory(&self) -> Option<(Address, Bytes)> {
+        self.factory.clone()
+    }
+
+    fn execute_batch(&self, calls: &[SafeCall]) -> Bytes {
+        ISimpleAccount::executeBatchCall {
+            dest: calls.iter().map(|call| call.to).collect(),
+            value: calls.iter().map(|call| call.value).collect(),
+            func: calls.iter().map(|call| call.data.clone()).collect(),
+        }
+        .abi_encode()
+        .into()
+    }
+
+    fn dummy_signature(&self) -> Bytes {
+        SIMPLE_ACCOUNT_DUMMY_SIGNATURE
+    }
+
+    async fn sign_user_operation(&self, hash: B256) -> Result<Bytes> {
+        let signature = self
+            .owner
+            .sign_message(hash.as_slice())
+            .await
+            .map_err(|e| Error::Provider(e.to_string()))?;
+        Ok(Bytes::copy_from_slice(&signature.as_bytes()))
+    }
+}
+
+/// The ERC-4337 bundler RPC methods a smart account burn needs
+#[allow(async_fn_in_trait)]
+pub trait Bundler {
+    /// `eth_estimateUserOperationGas`, `op` carries a dummy signature
+    async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGas>;
+
+    /// `eth_sendUserOperation`, returns the operation hash
+    async fn send_user_operation(&self, op: &UserOperation, entry_point: Address) -> Result<B256>;
+
+    /// `eth_getUserOperationReceipt`, `None` until the operation is included
+    async fn user_operation_receipt(&self, hash: B256) -> Result<Option<UserOperationReceipt>>;
+}
+
+/// A [`Bundler`] reached over JSON-RPC on HTTP
+#[derive(Clone, Debug)]
+pub struct HttpBundler {
+    client: Client,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Value,
+    error: Option<RpcErrorObject>,
+}
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+impl HttpBundler {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+        }
+    }
+
+    /// Sends the requests with `client`
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        debug!("bundler {method}");
+        let response: RpcResponse = self
+            .client
+            .post(&self.url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.error {
+            return Err(Error::Bundler {
+                code: error.code,
+                message: error.message,
+            });
+        }
+        Ok(serde_json::from_value(response.result)?)
+    }
+}
+
+impl Bundler for HttpBundler {
+    async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGas> {
+        self.request("eth_estimateUserOperationGas", json!([op, entry_point]))
+            .await
+    }
+
+    async fn send_user_operation(&self, op: &UserOperation, entry_point: Address) -> Result<B256> {
+        self.request("eth_sendUserOperation", json!([op, entry_point]))
+            .await
+    }
+
+    async fn user_operation_receipt(&self, hash: B256) -> Result<Option<UserOperationReceipt>> {
+        self.request("eth_getUserOperationReceipt", json!([hash]))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        alloy_signer_local::PrivateKeySigner,
+        std::{
+            io::{Read, Write},
+            net::TcpListener,
+            thread,
+        },
+    };
+
+    fn user_operation() -> UserOperation {
+        UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(3),
+            call_data: Bytes::from_static(&[0xab]),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(50_000),
+            pre_verification_gas: U256::from(21_000),
+            max_fee_per_gas: U256::from(2_000_000_000u64),
+            max_priority_fee_per_gas: U256::from(1_000_000),
+            ..Default::default()
+        }
+    }
+
+    /// Serves one JSON-RPC request with `result`, returning the request body
+    fn bundler_stand_in(result: Value) -> (String, thread::JoinHandle<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body = loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                    continue;
+                };
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or_default();
+                if body.len() >= length {
+                    break body.to_string();
+                }
+            };
+            let response = json!({"jsonrpc": "2.0", "id": 1, "result": result}).to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: \
+                 {}\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+            serde_json::from_str(&body).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_user_operation_json() -> anyhow::Result<()> {
+        let op = user_operation();
+        let json = serde_json::to_value(&op)?;
+        assert_eq!(json["nonce"], "0x3");
+        assert_eq!(json["callGasLimit"], "0x186a0");
+        assert_eq!(json["callData"], "0xab");
+        assert!(json.get("factory").is_none());
+        assert_eq!(serde_json::from_value::<UserOperation>(json)?, op);
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_operation_hash() {
+        let op = user_operation();
+        let hash = op.hash(ENTRY_POINT_V07_ADDRESS, 8453);
+        assert_ne!(hash, op.hash(ENTRY_POINT_V07_ADDRESS, 1));
+        assert_ne!(hash, op.hash(Address::ZERO, 8453));
+        let deployed = UserOperation {
+            factory: Some(Address::repeat_byte(0x22)),
+            ..op.clone()
+        };
+        assert_ne!(hash, deployed.hash(ENTRY_POINT_V07_ADDRESS, 8453));
+        let signed = UserOperation {
+            signature: Bytes::from_static(&[1]),
+            ..op
+        };
+        assert_eq!(hash, signed.hash(ENTRY_POINT_V07_ADDRESS, 8453));
+    }
+
+    #[tokio::test]
+    async fn test_simple_account() -> anyhow::Result<()> {
+        let owner = PrivateKeySigner::random();
+        let account = SimpleAccount::new(Address::repeat_byte(0x11), Arc::new(owner.clone()));
+        let calls = [SafeCall {
+            to: Address::repeat_byte(0x22),
+            value: U256::ZERO,
+            data: Bytes::from_static(&[0xab]),
+        }];
+        let decoded = ISimpleAccount::executeBatchCall::abi_decode(&account.execute_batch(&calls))?;
+        assert_eq!(decoded.dest, vec![calls[0].to]);
+        assert_eq!(decoded.func, vec![calls[0].data.clone()]);
+
+        let hash = user_operation().hash(account.entry_point(), 8453);
+        let signature = account.sign_user_operation(hash).await?;
+        let signature = alloy_primitives::Signature::try_from(signature.as_ref())?;
+        assert_eq!(
+            signature.recover_address_from_msg(hash.as_slice())?,
+            owner.address()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_bundler() -> anyhow::Result<()> {
+        let op_hash = B256::repeat_byte(0x44);
+        let (url, stand_in) = bundler_stand_in(json!(op_hash));
+        let bundler = HttpBundler::new(url);
+        let sent = bundler
+            .send_user_operation(&user_operation(), ENTRY_POINT_V07_ADDRESS)
+            .await?;
+        assert_eq!(sent, op_hash);
+        let request = stand_in.join().unwrap();
+        assert_eq!(request["method"], "eth_sendUserOperation");
+        assert_eq!(request["params"][0]["nonce"], "0x3");
+
+        let (url, stand_in) = bundler_stand_in(Value::Null);
+        let receipt = HttpBundler::new(url)
+            .user_operation_receipt(op_hash)
+            .await?;
+        assert_eq!(receipt, None);
+        stand_in.join().unwrap();
+        Ok(())
+    }
+}
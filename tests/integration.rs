@@ -4,7 +4,7 @@ use {
     alloy_provider::{Provider, ProviderBuilder, WalletProvider},
     alloy_signer_local::PrivateKeySigner,
     anyhow::Result,
-    cctp_client::{Cctp, SolanSigners, SolanaWrapper},
+    cctp_client::{Cctp, SolanSigners, SolanaRecipientAccount, SolanaWrapper, TransferMode},
     solana_commitment_config::CommitmentConfig,
     solana_keypair::Keypair,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
@@ -77,8 +77,8 @@ async fn test_reclaim() -> Result<()> {
     let (owner, rpc) = solana_setup()?;
     let rpc: SolanaWrapper = rpc.into();
 
-    let bridge = Cctp::new_reclaim(rpc.clone(), rpc, cctp_client::SOLANA_DEVNET);
-    let result = bridge.reclaim(&owner).await?;
+    let bridge = Cctp::new_reclaim(rpc, cctp_client::SOLANA_DEVNET)?;
+    let result = bridge.reclaim(&owner).await?.broadcast()?;
     info!("reclaimed {} accounts", result.len());
     for (sig, addr) in result {
         info!("reclaimed account {} with signature {}", addr, sig);
@@ -101,7 +101,9 @@ async fn test_burn_too_much() -> Result<()> {
         recipient,
     );
     let too_much: u64 = usdc_amount(10_000_000_000);
-    let result = bridge.burn(U256::from(too_much), None, None, None).await;
+    let result = bridge
+        .burn(U256::from(too_much), None, TransferMode::Standard)
+        .await;
     assert!(result.is_err(), "Should fail with insufficient balance");
 
     let e = result.unwrap_err();
@@ -124,14 +126,17 @@ async fn test_evm_burn_recv_split() -> Result<()> {
         NamedChain::BaseSepolia,
         recipient,
     );
-    let (burn_hash, approval_hash) = bridge.burn(U256::from(15), None, None, None).await?;
+    let (burn, approval) = bridge
+        .burn(U256::from(15), None, TransferMode::Standard)
+        .await?
+        .broadcast()?;
+    let burn_hash = burn.tx;
     assert!(!burn_hash.is_zero(), "Burn hash should not be zero");
-    info!(
-        "burn {burn_hash} approval {}",
-        approval_hash.unwrap_or_default()
-    );
+    assert!(approval.allowance >= U256::from(15));
+    info!("burn {burn_hash} approval {approval}");
     let attest = bridge.get_attestation_evm(burn_hash, None, None).await?;
-    let (recv_attest, recv_hash) = bridge.recv(burn_hash, None, None).await?;
+    let (recv_attest, recv) = bridge.recv(burn_hash, None, None).await?.broadcast()?;
+    let recv_hash = recv.tx;
     assert!(!recv_hash.is_zero(), "Receive hash should not be zero");
     assert!(
         !attest.attestation.is_empty(),
@@ -158,8 +163,15 @@ async fn test_evm_sol() -> Result<()> {
         cctp_client::SOLANA_DEVNET,
     );
     let result = bridge
-        .bridge_evm_sol(&owner, U256::from(10), None, None, None)
-        .await?;
+        .bridge_evm_sol(
+            &owner,
+            SolanaRecipientAccount::CreateAssociated { payer: None },
+            U256::from(10),
+            None,
+            TransferMode::Standard,
+        )
+        .await?
+        .broadcast()?;
     info!("bridge result {}", result);
     Ok(())
 }
@@ -178,8 +190,9 @@ async fn test_sol_evm() -> Result<()> {
         NamedChain::BaseSepolia,
     );
     let result = bridge
-        .bridge_sol_evm(10, SolanSigners::new(owner), None, None, None)
-        .await?;
+        .bridge_sol_evm(10, SolanSigners::new(owner), None, TransferMode::Standard)
+        .await?
+        .broadcast()?;
 
     info!("bridge result {}", result);
     Ok(())